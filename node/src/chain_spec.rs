@@ -1,7 +1,8 @@
 use sp_core::{Pair, Public, sr25519};
 use node_template_runtime::{
 	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
-	SudoConfig, SystemConfig, WASM_BINARY, Signature, PalletERC20Config
+	SudoConfig, SystemConfig, WASM_BINARY, Signature, PalletERC20Config,
+	StableTokenConfig, GovTokenConfig,
 };
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_finality_grandpa::AuthorityId as GrandpaId;
@@ -154,10 +155,31 @@ fn testnet_genesis(
 			key: root_key,
 		}),
 		pallet_erc20: Some(PalletERC20Config{
-			balances: endowed_accounts.into_iter().map(|k|(k, 1 << 60)).collect(),
+			phantom: Default::default(),
+			balances: endowed_accounts.iter().cloned().map(|k|(k, 1 << 60)).collect(),
 			name: vec![83, 111, 114, 97, 84, 101, 115, 116, 84, 111, 107, 101, 110],
 			sym: vec![83, 84, 84],
 			decimals: 18,
+			cap: None,
+			vesting: vec![],
+		}),
+		pallet_erc20_Instance1: Some(StableTokenConfig{
+			phantom: Default::default(),
+			balances: endowed_accounts.iter().cloned().map(|k|(k, 1 << 40)).collect(),
+			name: b"SoraStableToken".to_vec(),
+			sym: b"SST".to_vec(),
+			decimals: 6,
+			cap: None,
+			vesting: vec![],
+		}),
+		pallet_erc20_Instance2: Some(GovTokenConfig{
+			phantom: Default::default(),
+			balances: endowed_accounts.into_iter().map(|k|(k, 1 << 50)).collect(),
+			name: b"SoraGovToken".to_vec(),
+			sym: b"SGT".to_vec(),
+			decimals: 12,
+			cap: None,
+			vesting: vec![],
 		}),
 	}
 }
@@ -0,0 +1,41 @@
+//! Runtime API definition for the ERC20 pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// Read access to the token state of `pallet_erc20`, `instance` selects the pallet
+	/// instance in the order the runtime declares them, `0` being the default one.
+	/// Every method returns `None` for an instance the runtime does not have
+	#[api_version(2)]
+	pub trait Erc20Api<AccountId, AssetId, Balance> where
+		AccountId: Codec,
+		AssetId: Codec,
+		Balance: Codec,
+	{
+		/// Balance of `who` in token `asset_id`
+		fn balance_of(instance: u8, asset_id: AssetId, who: AccountId) -> Option<Balance>;
+		/// Allowance of `spender` over the `owner` s tokens
+		fn allowance(instance: u8, asset_id: AssetId, owner: AccountId, spender: AccountId) -> Option<Balance>;
+		/// Total supply of token `asset_id`
+		fn total_supply(instance: u8, asset_id: AssetId) -> Option<Balance>;
+		/// Name byte vector
+		fn name(instance: u8, asset_id: AssetId) -> Option<Vec<u8>>;
+		/// Symbol byte vector
+		fn symbol(instance: u8, asset_id: AssetId) -> Option<Vec<u8>>;
+		/// Decimals
+		fn decimals(instance: u8, asset_id: AssetId) -> Option<u8>;
+		/// Balance of `who` when snapshot `snapshot_id` was taken, the inner `None` for unknown snapshot
+		fn balance_of_at(instance: u8, asset_id: AssetId, who: AccountId, snapshot_id: u32) -> Option<Option<Balance>>;
+		/// Total supply when snapshot `snapshot_id` was taken, the inner `None` for unknown snapshot
+		fn total_supply_at(instance: u8, asset_id: AssetId, snapshot_id: u32) -> Option<Option<Balance>>;
+		/// Spenders of the `owner` s tokens with their allowances
+		fn allowances(instance: u8, asset_id: AssetId, owner: AccountId) -> Option<Vec<(AccountId, Balance)>>;
+	}
+}
@@ -0,0 +1,296 @@
+//! RPC interface for the ERC20 pallet.
+
+use std::{convert::TryInto, sync::Arc};
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, MaybeDisplay},
+};
+
+pub use pallet_erc20_rpc_runtime_api::Erc20Api as Erc20RuntimeApi;
+
+/// ERC20 token state RPC methods, balances of type `Balance` are returned as `NumberOrHex`.
+/// The optional trailing `instance` selects the token pallet instance, the default one when omitted.
+/// An instance the runtime does not have is an `UnknownInstance` error
+#[rpc]
+pub trait Erc20Api<BlockHash, AccountId, AssetId, Balance> {
+	/// Balance of `who` in token `asset_id`
+	#[rpc(name = "erc20_balanceOf")]
+	fn balance_of(
+		&self,
+		asset_id: AssetId,
+		who: AccountId,
+		at: Option<BlockHash>,
+		instance: Option<u8>,
+	) -> Result<NumberOrHex>;
+
+	/// Allowance of `spender` over the `owner` s tokens
+	#[rpc(name = "erc20_allowance")]
+	fn allowance(
+		&self,
+		asset_id: AssetId,
+		owner: AccountId,
+		spender: AccountId,
+		at: Option<BlockHash>,
+		instance: Option<u8>,
+	) -> Result<NumberOrHex>;
+
+	/// Total supply of token `asset_id`
+	#[rpc(name = "erc20_totalSupply")]
+	fn total_supply(&self, asset_id: AssetId, at: Option<BlockHash>, instance: Option<u8>) -> Result<NumberOrHex>;
+
+	/// Name of token `asset_id`
+	#[rpc(name = "erc20_name")]
+	fn name(&self, asset_id: AssetId, at: Option<BlockHash>, instance: Option<u8>) -> Result<Bytes>;
+
+	/// Symbol of token `asset_id`
+	#[rpc(name = "erc20_symbol")]
+	fn symbol(&self, asset_id: AssetId, at: Option<BlockHash>, instance: Option<u8>) -> Result<Bytes>;
+
+	/// Decimals of token `asset_id`
+	#[rpc(name = "erc20_decimals")]
+	fn decimals(&self, asset_id: AssetId, at: Option<BlockHash>, instance: Option<u8>) -> Result<u8>;
+
+	/// Balance of `who` when snapshot `snapshot_id` was taken, `null` for unknown snapshot
+	#[rpc(name = "erc20_balanceOfAt")]
+	fn balance_of_at(
+		&self,
+		asset_id: AssetId,
+		who: AccountId,
+		snapshot_id: u32,
+		at: Option<BlockHash>,
+		instance: Option<u8>,
+	) -> Result<Option<NumberOrHex>>;
+
+	/// Total supply when snapshot `snapshot_id` was taken, `null` for unknown snapshot
+	#[rpc(name = "erc20_totalSupplyAt")]
+	fn total_supply_at(
+		&self,
+		asset_id: AssetId,
+		snapshot_id: u32,
+		at: Option<BlockHash>,
+		instance: Option<u8>,
+	) -> Result<Option<NumberOrHex>>;
+
+	/// Spenders of the `owner` s tokens with their allowances
+	#[rpc(name = "erc20_allowances")]
+	fn allowances(
+		&self,
+		asset_id: AssetId,
+		owner: AccountId,
+		at: Option<BlockHash>,
+		instance: Option<u8>,
+	) -> Result<Vec<(AccountId, NumberOrHex)>>;
+}
+
+/// A struct that implements the [`Erc20Api`].
+pub struct Erc20<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Erc20<C, B> {
+	/// Create new `Erc20` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The balance does not fit into the RPC number type.
+	BalanceConversionError,
+	/// The runtime has no token pallet instance with the given index.
+	UnknownInstance,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::BalanceConversionError => 2,
+			Error::UnknownInstance => 3,
+		}
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: "Unable to query token state.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+fn unknown_instance(instance: u8) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::UnknownInstance.into()),
+		message: format!("No token pallet instance {}.", instance),
+		data: None,
+	}
+}
+
+fn to_number_or_hex<Balance: MaybeDisplay + TryInto<NumberOrHex> + Copy>(balance: Balance) -> Result<NumberOrHex> {
+	balance.try_into().map_err(|_| RpcError {
+		code: ErrorCode::ServerError(Error::BalanceConversionError.into()),
+		message: format!("{} doesn't fit in NumberOrHex representation", balance),
+		data: None,
+	})
+}
+
+impl<C, Block, AccountId, AssetId, Balance> Erc20Api<<Block as BlockT>::Hash, AccountId, AssetId, Balance>
+	for Erc20<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: Erc20RuntimeApi<Block, AccountId, AssetId, Balance>,
+	AccountId: Codec,
+	AssetId: Codec,
+	Balance: Codec + MaybeDisplay + TryInto<NumberOrHex> + Copy,
+{
+	fn balance_of(
+		&self,
+		asset_id: AssetId,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+		instance: Option<u8>,
+	) -> Result<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let instance = instance.unwrap_or_default();
+
+		let balance = api.balance_of(&at, instance, asset_id, who)
+			.map_err(runtime_error)?
+			.ok_or_else(|| unknown_instance(instance))?;
+		to_number_or_hex(balance)
+	}
+
+	fn allowance(
+		&self,
+		asset_id: AssetId,
+		owner: AccountId,
+		spender: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+		instance: Option<u8>,
+	) -> Result<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let instance = instance.unwrap_or_default();
+
+		let allowance = api.allowance(&at, instance, asset_id, owner, spender)
+			.map_err(runtime_error)?
+			.ok_or_else(|| unknown_instance(instance))?;
+		to_number_or_hex(allowance)
+	}
+
+	fn total_supply(
+		&self,
+		asset_id: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+		instance: Option<u8>,
+	) -> Result<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let instance = instance.unwrap_or_default();
+
+		let total_supply = api.total_supply(&at, instance, asset_id)
+			.map_err(runtime_error)?
+			.ok_or_else(|| unknown_instance(instance))?;
+		to_number_or_hex(total_supply)
+	}
+
+	fn name(&self, asset_id: AssetId, at: Option<<Block as BlockT>::Hash>, instance: Option<u8>) -> Result<Bytes> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let instance = instance.unwrap_or_default();
+
+		api.name(&at, instance, asset_id)
+			.map_err(runtime_error)?
+			.map(Into::into)
+			.ok_or_else(|| unknown_instance(instance))
+	}
+
+	fn symbol(&self, asset_id: AssetId, at: Option<<Block as BlockT>::Hash>, instance: Option<u8>) -> Result<Bytes> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let instance = instance.unwrap_or_default();
+
+		api.symbol(&at, instance, asset_id)
+			.map_err(runtime_error)?
+			.map(Into::into)
+			.ok_or_else(|| unknown_instance(instance))
+	}
+
+	fn decimals(&self, asset_id: AssetId, at: Option<<Block as BlockT>::Hash>, instance: Option<u8>) -> Result<u8> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let instance = instance.unwrap_or_default();
+
+		api.decimals(&at, instance, asset_id)
+			.map_err(runtime_error)?
+			.ok_or_else(|| unknown_instance(instance))
+	}
+
+	fn balance_of_at(
+		&self,
+		asset_id: AssetId,
+		who: AccountId,
+		snapshot_id: u32,
+		at: Option<<Block as BlockT>::Hash>,
+		instance: Option<u8>,
+	) -> Result<Option<NumberOrHex>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let instance = instance.unwrap_or_default();
+
+		let balance = api.balance_of_at(&at, instance, asset_id, who, snapshot_id)
+			.map_err(runtime_error)?
+			.ok_or_else(|| unknown_instance(instance))?;
+		balance.map(to_number_or_hex).transpose()
+	}
+
+	fn total_supply_at(
+		&self,
+		asset_id: AssetId,
+		snapshot_id: u32,
+		at: Option<<Block as BlockT>::Hash>,
+		instance: Option<u8>,
+	) -> Result<Option<NumberOrHex>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let instance = instance.unwrap_or_default();
+
+		let total_supply = api.total_supply_at(&at, instance, asset_id, snapshot_id)
+			.map_err(runtime_error)?
+			.ok_or_else(|| unknown_instance(instance))?;
+		total_supply.map(to_number_or_hex).transpose()
+	}
+
+	fn allowances(
+		&self,
+		asset_id: AssetId,
+		owner: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+		instance: Option<u8>,
+	) -> Result<Vec<(AccountId, NumberOrHex)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let instance = instance.unwrap_or_default();
+
+		let allowances = api.allowances(&at, instance, asset_id, owner)
+			.map_err(runtime_error)?
+			.ok_or_else(|| unknown_instance(instance))?;
+		allowances.into_iter()
+			.map(|(spender, allowance)| to_number_or_hex(allowance).map(|allowance| (spender, allowance)))
+			.collect()
+	}
+}
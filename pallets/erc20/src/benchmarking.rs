@@ -0,0 +1,481 @@
+//! Benchmarks for the ERC20 pallet
+
+use super::*;
+use frame_benchmarking::{benchmarks, account, whitelisted_caller};
+use frame_support::{
+	dispatch::Vec,
+	traits::{EnsureOrigin, Get},
+	sp_runtime::traits::{Bounded, One, Saturating, Zero},
+	sp_std::{self, vec},
+};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+/// Registers a token owned by `owner` holding the whole initial supply
+fn create_default_token<T: Config>(owner: &T::AccountId) -> T::AssetId {
+	let asset_id = NextAssetId::<T>::get();
+	assert!(Pallet::<T>::create_token(
+		T::CreateOrigin::successful_origin(),
+		owner.clone(),
+		b"BenchmarkToken".to_vec(),
+		b"BT".to_vec(),
+		DEFAULT_DECIMALS,
+		default_supply::<T>(),
+	).is_ok());
+	asset_id
+}
+
+fn default_supply<T: Config>() -> T::Balance {
+	default_amount::<T>().saturating_mul(T::Balance::from(1_000_000u32))
+}
+
+fn default_amount<T: Config>() -> T::Balance {
+	T::MinimumBalance::get().max(T::Balance::from(1_000u32))
+}
+
+/// Sets up the most expensive transfer of `amount` from `from` to `to`: a snapshot is pending,
+/// both accounts delegate to different accounts and the sender is left with dust when MinimumBalance allows it
+fn setup_worst_case_transfer<T: Config>(
+	asset_id: T::AssetId,
+	from: &T::AccountId,
+	to: &T::AccountId,
+	amount: T::Balance
+) -> Result<(), &'static str> {
+	let dust = T::MinimumBalance::get().saturating_sub(One::one());
+	Pallet::<T>::_mint(asset_id, from.clone(), amount.saturating_add(dust))?;
+	Pallet::<T>::_delegate(asset_id, from.clone(), account("delegatee", 0, SEED))?;
+	Pallet::<T>::_delegate(asset_id, to.clone(), account("delegatee", 1, SEED))?;
+	CurrentSnapshotId::<T>::insert(asset_id, 1);
+	Ok(())
+}
+
+benchmarks! {
+	transfer {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let asset_id = create_default_token::<T>(&owner);
+		let caller: T::AccountId = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let amount = default_amount::<T>();
+		setup_worst_case_transfer::<T>(asset_id, &caller, &recipient, amount)?;
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, recipient.clone(), amount)
+	verify {
+		assert_eq!(BalanceOf::<T>::get(asset_id, &recipient), amount);
+		assert!(BalanceOf::<T>::get(asset_id, &caller).is_zero());
+	}
+
+	approve {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = create_default_token::<T>(&caller);
+		let spender: T::AccountId = account("spender", 0, SEED);
+		let amount = default_amount::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, spender.clone(), amount)
+	verify {
+		assert_eq!(AllowanceOf::<T>::get((asset_id, &caller), &spender), amount);
+	}
+
+	approve_with_expiry {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = create_default_token::<T>(&caller);
+		let spender: T::AccountId = account("spender", 0, SEED);
+		let amount = default_amount::<T>();
+		let expires_at = T::BlockNumber::max_value();
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, spender.clone(), amount, expires_at)
+	verify {
+		assert_eq!(AllowanceOf::<T>::get((asset_id, &caller), &spender), amount);
+		assert_eq!(AllowanceExpiry::<T>::get((asset_id, &caller), &spender), Some(expires_at));
+	}
+
+	approve_checked {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = create_default_token::<T>(&caller);
+		let spender: T::AccountId = account("spender", 0, SEED);
+		let amount = default_amount::<T>();
+		Pallet::<T>::_approve(asset_id, caller.clone(), spender.clone(), amount)?;
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, spender.clone(), amount, amount + amount)
+	verify {
+		assert_eq!(AllowanceOf::<T>::get((asset_id, &caller), &spender), amount + amount);
+	}
+
+	revoke_allowance {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = create_default_token::<T>(&caller);
+		let spender: T::AccountId = account("spender", 0, SEED);
+		Pallet::<T>::_approve_with_expiry(
+			asset_id, caller.clone(), spender.clone(), default_amount::<T>(), Some(T::BlockNumber::max_value())
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, spender.clone())
+	verify {
+		assert!(!AllowanceOf::<T>::contains_key((asset_id, &caller), &spender));
+	}
+
+	revoke_all_allowances {
+		let n in 1 .. T::MaxSpenders::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = create_default_token::<T>(&caller);
+		for i in 0..n {
+			let spender: T::AccountId = account("spender", i, SEED);
+			Pallet::<T>::_approve_with_expiry(
+				asset_id, caller.clone(), spender, default_amount::<T>(), Some(T::BlockNumber::max_value())
+			)?;
+		}
+	}: _(RawOrigin::Signed(caller.clone()), asset_id)
+	verify {
+		assert!(Pallet::<T>::allowances_of(asset_id, &caller).is_empty());
+	}
+
+	vest {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = create_default_token::<T>(&caller);
+		let schedule = VestingInfo {
+			locked: default_amount::<T>(),
+			per_block: One::one(),
+			starting_block: Zero::zero(),
+			cliff: Zero::zero(),
+		};
+		VestingSchedules::<T>::insert(asset_id, &caller, schedule);
+		frame_system::Module::<T>::set_block_number(T::BlockNumber::from(10u32));
+	}: _(RawOrigin::Signed(caller.clone()), asset_id)
+	verify {
+		assert!(VestingSchedules::<T>::contains_key(asset_id, &caller));
+	}
+
+	vested_transfer {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = create_default_token::<T>(&caller);
+		let target: T::AccountId = account("target", 0, SEED);
+		let locked = default_amount::<T>().max(T::MinVestedTransfer::get());
+		Pallet::<T>::_mint(asset_id, caller.clone(), locked)?;
+		let schedule = VestingInfo {
+			locked,
+			per_block: One::one(),
+			starting_block: Zero::zero(),
+			cliff: Zero::zero(),
+		};
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, target.clone(), schedule)
+	verify {
+		assert_eq!(VestingSchedules::<T>::get(asset_id, &target), Some(schedule));
+	}
+
+	transfer_all {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = create_default_token::<T>(&caller);
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, recipient.clone())
+	verify {
+		assert_eq!(BalanceOf::<T>::get(asset_id, &recipient), default_supply::<T>());
+	}
+
+	transfer_keep_alive {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = create_default_token::<T>(&caller);
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let amount = default_amount::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, recipient.clone(), amount)
+	verify {
+		assert_eq!(BalanceOf::<T>::get(asset_id, &recipient), amount);
+	}
+
+	transfer_batch {
+		let n in 1 .. T::MaxBatchSize::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = create_default_token::<T>(&caller);
+		let amount = default_amount::<T>();
+		let transfers: Vec<(T::AccountId, T::Balance)> = (0..n)
+			.map(|i| (account("recipient", i, SEED), amount))
+			.collect();
+		let last_recipient: T::AccountId = account("recipient", n - 1, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, transfers)
+	verify {
+		assert_eq!(BalanceOf::<T>::get(asset_id, &last_recipient), amount);
+	}
+
+	transfer_from_batch {
+		let n in 1 .. T::MaxBatchSize::get();
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let asset_id = create_default_token::<T>(&owner);
+		let caller: T::AccountId = whitelisted_caller();
+		let amount = default_amount::<T>();
+		Pallet::<T>::_approve(asset_id, owner.clone(), caller.clone(), default_supply::<T>())?;
+		let transfers: Vec<(T::AccountId, T::Balance)> = (0..n)
+			.map(|i| (account("recipient", i, SEED), amount))
+			.collect();
+		let last_recipient: T::AccountId = account("recipient", n - 1, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, owner.clone(), transfers)
+	verify {
+		assert_eq!(BalanceOf::<T>::get(asset_id, &last_recipient), amount);
+	}
+
+	transfer_from {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let asset_id = create_default_token::<T>(&owner);
+		let caller: T::AccountId = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let from: T::AccountId = account("from", 0, SEED);
+		let amount = default_amount::<T>();
+		setup_worst_case_transfer::<T>(asset_id, &from, &recipient, amount)?;
+		Pallet::<T>::_approve(asset_id, from.clone(), caller.clone(), default_supply::<T>())?;
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, from.clone(), recipient.clone(), amount)
+	verify {
+		assert_eq!(BalanceOf::<T>::get(asset_id, &recipient), amount);
+		assert!(BalanceOf::<T>::get(asset_id, &from).is_zero());
+		assert_eq!(AllowanceOf::<T>::get((asset_id, &from), &caller), default_supply::<T>() - amount);
+	}
+
+	increase_allowance {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = create_default_token::<T>(&caller);
+		let spender: T::AccountId = account("spender", 0, SEED);
+		let amount = default_amount::<T>();
+		Pallet::<T>::_approve(asset_id, caller.clone(), spender.clone(), amount)?;
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, spender.clone(), amount)
+	verify {
+		assert_eq!(AllowanceOf::<T>::get((asset_id, &caller), &spender), amount + amount);
+	}
+
+	decrease_allowance {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = create_default_token::<T>(&caller);
+		let spender: T::AccountId = account("spender", 0, SEED);
+		let amount = default_amount::<T>();
+		Pallet::<T>::_approve(asset_id, caller.clone(), spender.clone(), amount + amount)?;
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, spender.clone(), amount)
+	verify {
+		assert_eq!(AllowanceOf::<T>::get((asset_id, &caller), &spender), amount);
+	}
+
+	mint {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let asset_id = create_default_token::<T>(&owner);
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let amount = default_amount::<T>();
+		let origin = T::MintOrigin::successful_origin();
+	}: {
+		Pallet::<T>::mint(origin, asset_id, recipient.clone(), amount)?;
+	}
+	verify {
+		assert_eq!(BalanceOf::<T>::get(asset_id, &recipient), amount);
+		assert_eq!(TotalSupply::<T>::get(asset_id), default_supply::<T>() + amount);
+	}
+
+	burn {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let asset_id = create_default_token::<T>(&owner);
+		let amount = default_amount::<T>();
+		let origin = T::BurnOrigin::successful_origin();
+	}: {
+		Pallet::<T>::burn(origin, asset_id, owner.clone(), amount)?;
+	}
+	verify {
+		assert_eq!(BalanceOf::<T>::get(asset_id, &owner), default_supply::<T>() - amount);
+	}
+
+	burn_self {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = create_default_token::<T>(&caller);
+		let amount = default_amount::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, amount)
+	verify {
+		assert_eq!(BalanceOf::<T>::get(asset_id, &caller), default_supply::<T>() - amount);
+	}
+
+	create_token {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let asset_id = NextAssetId::<T>::get();
+		let name = vec![0u8; T::StringLimit::get() as usize];
+		let symbol = vec![1u8; T::StringLimit::get() as usize];
+		let origin = T::CreateOrigin::successful_origin();
+	}: {
+		Pallet::<T>::create_token(origin, owner.clone(), name, symbol, DEFAULT_DECIMALS, T::Balance::max_value())?;
+	}
+	verify {
+		assert_eq!(Owner::<T>::get(asset_id), Some(owner.clone()));
+		assert_eq!(BalanceOf::<T>::get(asset_id, &owner), T::Balance::max_value());
+	}
+
+	set_metadata {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let asset_id = create_default_token::<T>(&owner);
+		let name = vec![0u8; T::StringLimit::get() as usize];
+		let symbol = vec![1u8; T::StringLimit::get() as usize];
+		let origin = T::MetadataOrigin::successful_origin();
+	}: {
+		Pallet::<T>::set_metadata(origin, asset_id, name.clone(), symbol.clone(), 6)?;
+	}
+	verify {
+		assert_eq!(Name::<T>::get(asset_id), name);
+		assert_eq!(Symbol::<T>::get(asset_id), symbol);
+		assert_eq!(Decimals::<T>::get(asset_id), 6);
+	}
+
+	permit {
+		let owner = T::BenchmarkHelper::signer();
+		let asset_id = create_default_token::<T>(&owner);
+		let caller: T::AccountId = whitelisted_caller();
+		let spender: T::AccountId = account("spender", 0, SEED);
+		let value = default_amount::<T>();
+		let deadline = T::BlockNumber::max_value();
+		let nonce = PermitNonces::<T>::get(&owner);
+		let payload = Pallet::<T>::permit_payload(asset_id, &owner, &spender, value, nonce, deadline);
+		let signature = T::BenchmarkHelper::sign(&owner, &payload);
+	}: _(RawOrigin::Signed(caller), asset_id, owner.clone(), spender.clone(), value, deadline, signature)
+	verify {
+		assert_eq!(AllowanceOf::<T>::get((asset_id, &owner), &spender), value);
+		assert_eq!(PermitNonces::<T>::get(&owner), nonce + One::one());
+	}
+
+	pause {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let asset_id = create_default_token::<T>(&owner);
+		let origin = T::PauseOrigin::successful_origin();
+	}: {
+		Pallet::<T>::pause(origin, asset_id)?;
+	}
+	verify {
+		assert!(Paused::<T>::get(asset_id));
+	}
+
+	unpause {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let asset_id = create_default_token::<T>(&owner);
+		Paused::<T>::insert(asset_id, true);
+		let origin = T::PauseOrigin::successful_origin();
+	}: {
+		Pallet::<T>::unpause(origin, asset_id)?;
+	}
+	verify {
+		assert!(!Paused::<T>::get(asset_id));
+	}
+
+	freeze_account {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let asset_id = create_default_token::<T>(&owner);
+		let origin = T::PauseOrigin::successful_origin();
+	}: {
+		Pallet::<T>::freeze_account(origin, asset_id, owner.clone())?;
+	}
+	verify {
+		assert!(Frozen::<T>::get(asset_id, &owner));
+	}
+
+	thaw_account {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let asset_id = create_default_token::<T>(&owner);
+		Frozen::<T>::insert(asset_id, &owner, true);
+		let origin = T::PauseOrigin::successful_origin();
+	}: {
+		Pallet::<T>::thaw_account(origin, asset_id, owner.clone())?;
+	}
+	verify {
+		assert!(!Frozen::<T>::get(asset_id, &owner));
+	}
+
+	set_cap {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let asset_id = create_default_token::<T>(&owner);
+		let cap = default_supply::<T>() + default_amount::<T>();
+		let origin = T::CapOrigin::successful_origin();
+	}: {
+		Pallet::<T>::set_cap(origin, asset_id, Some(cap))?;
+	}
+	verify {
+		assert_eq!(Cap::<T>::get(asset_id), Some(cap));
+	}
+
+	snapshot {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let asset_id = create_default_token::<T>(&owner);
+		let origin = T::SnapshotOrigin::successful_origin();
+	}: {
+		Pallet::<T>::snapshot(origin, asset_id)?;
+	}
+	verify {
+		assert_eq!(CurrentSnapshotId::<T>::get(asset_id), 1);
+	}
+
+	delegate {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = create_default_token::<T>(&caller);
+		let previous: T::AccountId = account("previous", 0, SEED);
+		let delegatee: T::AccountId = account("delegatee", 0, SEED);
+		Pallet::<T>::_delegate(asset_id, caller.clone(), previous)?;
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, delegatee.clone())
+	verify {
+		assert_eq!(Pallet::<T>::get_votes(asset_id, &delegatee), default_supply::<T>());
+	}
+
+	delegate_by_sig {
+		let delegator = T::BenchmarkHelper::signer();
+		let asset_id = create_default_token::<T>(&delegator);
+		let caller: T::AccountId = whitelisted_caller();
+		let previous: T::AccountId = account("previous", 0, SEED);
+		let delegatee: T::AccountId = account("delegatee", 0, SEED);
+		Pallet::<T>::_delegate(asset_id, delegator.clone(), previous)?;
+		let expiry = T::BlockNumber::max_value();
+		let nonce = PermitNonces::<T>::get(&delegator);
+		let payload = Pallet::<T>::delegation_payload(asset_id, &delegator, &delegatee, nonce, expiry);
+		let signature = T::BenchmarkHelper::sign(&delegator, &payload);
+	}: _(RawOrigin::Signed(caller), asset_id, delegator.clone(), delegatee.clone(), expiry, signature)
+	verify {
+		assert_eq!(Pallet::<T>::get_votes(asset_id, &delegatee), default_supply::<T>());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, set_minimum_balance, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_transfer::<Test>());
+			assert_ok!(test_benchmark_approve::<Test>());
+			assert_ok!(test_benchmark_approve_with_expiry::<Test>());
+			assert_ok!(test_benchmark_approve_checked::<Test>());
+			assert_ok!(test_benchmark_revoke_allowance::<Test>());
+			assert_ok!(test_benchmark_revoke_all_allowances::<Test>());
+			assert_ok!(test_benchmark_vest::<Test>());
+			assert_ok!(test_benchmark_vested_transfer::<Test>());
+			assert_ok!(test_benchmark_transfer_all::<Test>());
+			assert_ok!(test_benchmark_transfer_keep_alive::<Test>());
+			assert_ok!(test_benchmark_transfer_batch::<Test>());
+			assert_ok!(test_benchmark_transfer_from_batch::<Test>());
+			assert_ok!(test_benchmark_transfer_from::<Test>());
+			assert_ok!(test_benchmark_increase_allowance::<Test>());
+			assert_ok!(test_benchmark_decrease_allowance::<Test>());
+			assert_ok!(test_benchmark_mint::<Test>());
+			assert_ok!(test_benchmark_burn::<Test>());
+			assert_ok!(test_benchmark_burn_self::<Test>());
+			assert_ok!(test_benchmark_create_token::<Test>());
+			assert_ok!(test_benchmark_set_metadata::<Test>());
+			assert_ok!(test_benchmark_permit::<Test>());
+			assert_ok!(test_benchmark_pause::<Test>());
+			assert_ok!(test_benchmark_unpause::<Test>());
+			assert_ok!(test_benchmark_freeze_account::<Test>());
+			assert_ok!(test_benchmark_thaw_account::<Test>());
+			assert_ok!(test_benchmark_set_cap::<Test>());
+			assert_ok!(test_benchmark_snapshot::<Test>());
+			assert_ok!(test_benchmark_delegate::<Test>());
+			assert_ok!(test_benchmark_delegate_by_sig::<Test>());
+		});
+	}
+
+	#[test]
+	fn test_benchmarks_with_minimum_balance() {
+		new_test_ext().execute_with(|| {
+			set_minimum_balance(1_000_000_000);
+			assert_ok!(test_benchmark_transfer::<Test>());
+			assert_ok!(test_benchmark_transfer_from::<Test>());
+			assert_ok!(test_benchmark_transfer_keep_alive::<Test>());
+			assert_ok!(test_benchmark_transfer_all::<Test>());
+			assert_ok!(test_benchmark_transfer_batch::<Test>());
+			assert_ok!(test_benchmark_transfer_from_batch::<Test>());
+			assert_ok!(test_benchmark_vested_transfer::<Test>());
+			assert_ok!(test_benchmark_mint::<Test>());
+			assert_ok!(test_benchmark_burn::<Test>());
+			assert_ok!(test_benchmark_burn_self::<Test>());
+		});
+	}
+}
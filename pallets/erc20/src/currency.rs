@@ -0,0 +1,352 @@
+//! Reserves, locks and `Currency` family trait implementations for a single token of the registry
+
+use super::*;
+use frame_support::{
+	codec::{Encode, Decode},
+	dispatch::{DispatchError, DispatchResult},
+	traits::{
+		BalanceStatus, Currency, ExistenceRequirement, Get, Imbalance, LockIdentifier, LockableCurrency,
+		ReservableCurrency, SignedImbalance, TryDrop, WithdrawReasons,
+	},
+	sp_runtime::traits::{Bounded, CheckedAdd, CheckedSub, Saturating, Zero},
+	sp_std::{marker::PhantomData, mem},
+	ensure, RuntimeDebug,
+};
+
+/// A named lock on the free balance of an account
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct BalanceLock<Balance> {
+	/// Identifier of the lock, setting a lock with the same id replaces it
+	pub id: LockIdentifier,
+	/// Amount of free balance that can not be withdrawn for `reasons`
+	pub amount: Balance,
+	/// Withdrawals the lock applies to
+	pub reasons: WithdrawReasons,
+}
+
+/// Single token view of the registry: implements the `Currency` family of traits for
+/// the token with asset id `A`, so it can be used wherever a currency is expected
+pub struct TokenOf<T, I, A>(PhantomData<(T, I, A)>);
+
+/// Funds added to an account without being taken from anywhere, the total supply
+/// is increased when the imbalance is dropped
+#[must_use]
+pub struct PositiveImbalance<T: Config<I>, I: 'static, A: Get<T::AssetId>>(T::Balance, PhantomData<(I, A)>);
+
+/// Funds taken from an account without being put anywhere, the total supply
+/// is reduced when the imbalance is dropped
+#[must_use]
+pub struct NegativeImbalance<T: Config<I>, I: 'static, A: Get<T::AssetId>>(T::Balance, PhantomData<(I, A)>);
+
+macro_rules! impl_imbalance {
+	($name:ident, $opposite:ident, $on_drop:ident) => {
+		impl<T: Config<I>, I: 'static, A: Get<T::AssetId>> $name<T, I, A> {
+			/// Creates an imbalance of `amount`
+			pub fn new(amount: T::Balance) -> Self {
+				$name(amount, PhantomData)
+			}
+		}
+
+		impl<T: Config<I>, I: 'static, A: Get<T::AssetId>> Default for $name<T, I, A> {
+			fn default() -> Self {
+				Self::zero()
+			}
+		}
+
+		impl<T: Config<I>, I: 'static, A: Get<T::AssetId>> TryDrop for $name<T, I, A> {
+			fn try_drop(self) -> Result<(), Self> {
+				self.drop_zero()
+			}
+		}
+
+		impl<T: Config<I>, I: 'static, A: Get<T::AssetId>> Imbalance<T::Balance> for $name<T, I, A> {
+			type Opposite = $opposite<T, I, A>;
+
+			fn zero() -> Self {
+				Self::new(Zero::zero())
+			}
+
+			fn drop_zero(self) -> Result<(), Self> {
+				if self.0.is_zero() {
+					Ok(())
+				} else {
+					Err(self)
+				}
+			}
+
+			fn split(self, amount: T::Balance) -> (Self, Self) {
+				let first = self.0.min(amount);
+				let second = self.0 - first;
+				mem::forget(self);
+				(Self::new(first), Self::new(second))
+			}
+
+			fn merge(mut self, other: Self) -> Self {
+				self.0 = self.0.saturating_add(other.0);
+				mem::forget(other);
+				self
+			}
+
+			fn subsume(&mut self, other: Self) {
+				self.0 = self.0.saturating_add(other.0);
+				mem::forget(other);
+			}
+
+			fn offset(self, other: Self::Opposite) -> Result<Self, Self::Opposite> {
+				let (a, b) = (self.0, other.0);
+				mem::forget((self, other));
+				if a >= b {
+					Ok(Self::new(a - b))
+				} else {
+					Err(<$opposite<T, I, A>>::new(b - a))
+				}
+			}
+
+			fn peek(&self) -> T::Balance {
+				self.0
+			}
+		}
+
+		impl<T: Config<I>, I: 'static, A: Get<T::AssetId>> Drop for $name<T, I, A> {
+			fn drop(&mut self) {
+				if self.0.is_zero() {
+					return;
+				}
+				let asset_id = A::get();
+				Pallet::<T, I>::update_total_supply_snapshot(asset_id);
+				TotalSupply::<T, I>::mutate(asset_id, |total| *total = total.$on_drop(self.0));
+			}
+		}
+	};
+}
+
+impl_imbalance!(PositiveImbalance, NegativeImbalance, saturating_add);
+impl_imbalance!(NegativeImbalance, PositiveImbalance, saturating_sub);
+
+impl<T, I, A> Currency<<T as frame_system::Config>::AccountId> for TokenOf<T, I, A> where
+	T: Config<I>,
+	I: 'static,
+	A: Get<T::AssetId>,
+{
+	type Balance = T::Balance;
+	type PositiveImbalance = PositiveImbalance<T, I, A>;
+	type NegativeImbalance = NegativeImbalance<T, I, A>;
+
+	fn total_balance(who: &T::AccountId) -> Self::Balance {
+		Self::free_balance(who).saturating_add(Self::reserved_balance(who))
+	}
+
+	fn can_slash(who: &T::AccountId, value: Self::Balance) -> bool {
+		Self::free_balance(who) >= value
+	}
+
+	fn total_issuance() -> Self::Balance {
+		TotalSupply::<T, I>::get(A::get())
+	}
+
+	fn minimum_balance() -> Self::Balance {
+		T::MinimumBalance::get()
+	}
+
+	fn burn(amount: Self::Balance) -> Self::PositiveImbalance {
+		let asset_id = A::get();
+		Pallet::<T, I>::update_total_supply_snapshot(asset_id);
+		let amount = TotalSupply::<T, I>::mutate(asset_id, |total| {
+			let amount = amount.min(*total);
+			*total -= amount;
+			amount
+		});
+		PositiveImbalance::new(amount)
+	}
+
+	fn issue(amount: Self::Balance) -> Self::NegativeImbalance {
+		let asset_id = A::get();
+		if Pallet::<T, I>::ensure_asset_exists(asset_id).is_err() || Pallet::<T, I>::ensure_not_paused(asset_id).is_err() {
+			return NegativeImbalance::zero();
+		}
+		Pallet::<T, I>::update_total_supply_snapshot(asset_id);
+		let limit = Cap::<T, I>::get(asset_id).unwrap_or_else(T::Balance::max_value);
+		let amount = TotalSupply::<T, I>::mutate(asset_id, |total| {
+			let amount = limit.saturating_sub(*total).min(amount);
+			*total += amount;
+			amount
+		});
+		NegativeImbalance::new(amount)
+	}
+
+	fn free_balance(who: &T::AccountId) -> Self::Balance {
+		BalanceOf::<T, I>::get(A::get(), who)
+	}
+
+	fn ensure_can_withdraw(
+		who: &T::AccountId,
+		_amount: Self::Balance,
+		reasons: WithdrawReasons,
+		new_balance: Self::Balance,
+	) -> DispatchResult {
+		let asset_id = A::get();
+		if reasons.intersects(WithdrawReasons::TRANSFER) {
+			ensure!(new_balance >= Pallet::<T, I>::vesting_locked(asset_id, who), Error::<T, I>::LockedByVesting);
+		}
+		ensure!(
+			new_balance >= Pallet::<T, I>::locked_balance(asset_id, who, reasons),
+			Error::<T, I>::LiquidityRestrictions
+		);
+		Ok(())
+	}
+
+	fn transfer(
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		value: Self::Balance,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		Pallet::<T, I>::_transfer_with_existence(A::get(), source.clone(), dest.clone(), value, existence_requirement)
+			.map_err(|e| e.error)?;
+		Ok(())
+	}
+
+	fn slash(who: &T::AccountId, value: Self::Balance) -> (Self::NegativeImbalance, Self::Balance) {
+		let asset_id = A::get();
+		let free = Self::free_balance(who);
+		let from_free = free.min(value);
+		Pallet::<T, I>::update_account_snapshot(asset_id, who);
+		Pallet::<T, I>::write_free_balance(asset_id, who, free - from_free);
+		let (imbalance, remaining) = Self::slash_reserved(who, value - from_free);
+		Pallet::<T, I>::move_delegated_votes(asset_id, Some(who), None, from_free);
+		(imbalance.merge(NegativeImbalance::new(from_free)), remaining)
+	}
+
+	fn deposit_into_existing(
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> Result<Self::PositiveImbalance, DispatchError> {
+		ensure!(!Self::total_balance(who).is_zero(), Error::<T, I>::DeadAccount);
+		Self::deposit(who, value)
+	}
+
+	fn deposit_creating(who: &T::AccountId, value: Self::Balance) -> Self::PositiveImbalance {
+		Self::deposit(who, value).unwrap_or_else(|_| PositiveImbalance::zero())
+	}
+
+	fn withdraw(
+		who: &T::AccountId,
+		value: Self::Balance,
+		reasons: WithdrawReasons,
+		liveness: ExistenceRequirement,
+	) -> Result<Self::NegativeImbalance, DispatchError> {
+		let asset_id = A::get();
+		let new_free = Self::free_balance(who)
+			.checked_sub(&value)
+			.ok_or(Error::<T, I>::InsufficientBalance)?;
+		ensure!(
+			liveness == ExistenceRequirement::AllowDeath
+				|| new_free.saturating_add(Self::reserved_balance(who)) >= T::MinimumBalance::get(),
+			Error::<T, I>::KeepAlive
+		);
+		Self::ensure_can_withdraw(who, value, reasons, new_free)?;
+		Pallet::<T, I>::update_account_snapshot(asset_id, who);
+		Pallet::<T, I>::write_free_balance(asset_id, who, new_free);
+		Pallet::<T, I>::move_delegated_votes(asset_id, Some(who), None, value);
+		Ok(NegativeImbalance::new(value))
+	}
+
+	fn make_free_balance_be(
+		who: &T::AccountId,
+		balance: Self::Balance,
+	) -> SignedImbalance<Self::Balance, Self::PositiveImbalance> {
+		let asset_id = A::get();
+		let free = Self::free_balance(who);
+		if balance > free {
+			let imbalance = Self::deposit(who, balance - free).unwrap_or_else(|_| PositiveImbalance::zero());
+			return SignedImbalance::Positive(imbalance);
+		}
+		Pallet::<T, I>::update_account_snapshot(asset_id, who);
+		Pallet::<T, I>::write_free_balance(asset_id, who, balance);
+		Pallet::<T, I>::move_delegated_votes(asset_id, Some(who), None, free - balance);
+		SignedImbalance::Negative(NegativeImbalance::new(free - balance))
+	}
+}
+
+impl<T, I, A> TokenOf<T, I, A> where
+	T: Config<I>,
+	I: 'static,
+	A: Get<T::AssetId>,
+{
+	/// Credits `value` to the free balance of `who` under the same checks as minting,
+	/// the total supply is increased when the returned imbalance is dropped
+	fn deposit(who: &T::AccountId, value: T::Balance) -> Result<PositiveImbalance<T, I, A>, DispatchError> {
+		let asset_id = A::get();
+		Pallet::<T, I>::ensure_asset_exists(asset_id)?;
+		Pallet::<T, I>::ensure_can_mint(asset_id, who, value)?;
+		let new_free = Self::free_balance(who).checked_add(&value).ok_or(Error::<T, I>::BalanceOverflow)?;
+		Pallet::<T, I>::update_account_snapshot(asset_id, who);
+		Pallet::<T, I>::write_free_balance(asset_id, who, new_free);
+		Pallet::<T, I>::move_delegated_votes(asset_id, None, Some(who), value);
+		Ok(PositiveImbalance::new(value))
+	}
+}
+
+impl<T, I, A> ReservableCurrency<<T as frame_system::Config>::AccountId> for TokenOf<T, I, A> where
+	T: Config<I>,
+	I: 'static,
+	A: Get<T::AssetId>,
+{
+	fn can_reserve(who: &T::AccountId, value: Self::Balance) -> bool {
+		match Self::free_balance(who).checked_sub(&value) {
+			Some(new_free) => Self::ensure_can_withdraw(who, value, WithdrawReasons::RESERVE, new_free).is_ok(),
+			None => false,
+		}
+	}
+
+	fn slash_reserved(who: &T::AccountId, value: Self::Balance) -> (Self::NegativeImbalance, Self::Balance) {
+		let asset_id = A::get();
+		let reserved = ReservedBalance::<T, I>::get(asset_id, who);
+		let actual = reserved.min(value);
+		Pallet::<T, I>::write_reserved_balance(asset_id, who, reserved - actual);
+		Pallet::<T, I>::move_delegated_votes(asset_id, Some(who), None, actual);
+		(NegativeImbalance::new(actual), value - actual)
+	}
+
+	fn reserved_balance(who: &T::AccountId) -> Self::Balance {
+		ReservedBalance::<T, I>::get(A::get(), who)
+	}
+
+	fn reserve(who: &T::AccountId, value: Self::Balance) -> DispatchResult {
+		Pallet::<T, I>::reserve(A::get(), who, value)
+	}
+
+	fn unreserve(who: &T::AccountId, value: Self::Balance) -> Self::Balance {
+		Pallet::<T, I>::unreserve(A::get(), who, value)
+	}
+
+	fn repatriate_reserved(
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		value: Self::Balance,
+		status: BalanceStatus,
+	) -> Result<Self::Balance, DispatchError> {
+		Pallet::<T, I>::repatriate_reserved(A::get(), slashed, beneficiary, value, status)
+	}
+}
+
+impl<T, I, A> LockableCurrency<<T as frame_system::Config>::AccountId> for TokenOf<T, I, A> where
+	T: Config<I>,
+	I: 'static,
+	A: Get<T::AssetId>,
+{
+	type Moment = T::BlockNumber;
+	type MaxLocks = T::MaxLocks;
+
+	fn set_lock(id: LockIdentifier, who: &T::AccountId, amount: Self::Balance, reasons: WithdrawReasons) {
+		Pallet::<T, I>::set_lock(A::get(), id, who, amount, reasons)
+	}
+
+	fn extend_lock(id: LockIdentifier, who: &T::AccountId, amount: Self::Balance, reasons: WithdrawReasons) {
+		Pallet::<T, I>::extend_lock(A::get(), id, who, amount, reasons)
+	}
+
+	fn remove_lock(id: LockIdentifier, who: &T::AccountId) {
+		Pallet::<T, I>::remove_lock(A::get(), id, who)
+	}
+}
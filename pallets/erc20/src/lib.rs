@@ -5,8 +5,22 @@
 mod mock;
 #[cfg(test)]
 mod tests;
+mod payment;
+mod votes;
+mod vesting;
+mod currency;
+pub mod migrations;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
 
 pub use pallet::*;
+pub use weights::WeightInfo;
+pub use payment::Erc20FeeAdapter;
+pub use votes::Votes;
+pub use vesting::VestingInfo;
+pub use currency::{BalanceLock, PositiveImbalance, NegativeImbalance, TokenOf};
+pub use frame_support::instances::{Instance1, Instance2};
 use frame_support::{
     codec::{Codec},
 	sp_runtime::sp_std::{fmt::Debug},
@@ -14,15 +28,63 @@ use frame_support::{
 
 pub const DEFAULT_DECIMALS: u8 = 18;
 
+/// Handler of dust removed from accounts left below the minimum balance.
+/// The dust is already burned when the handler is called, a handler keeping it
+/// has to mint it again, e.g. to a treasury account
+pub trait OnDust<AssetId, AccountId, Balance> {
+	fn on_dust(asset_id: AssetId, who: &AccountId, amount: Balance);
+}
+
+impl<AssetId, AccountId, Balance> OnDust<AssetId, AccountId, Balance> for () {
+	fn on_dust(_asset_id: AssetId, _who: &AccountId, _amount: Balance) {}
+}
+
+/// Signs permit and delegation payloads in benchmarks, the signature scheme being up to the runtime
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AccountId, Signature> {
+	/// Account of a new key the benchmark signs with
+	fn signer() -> AccountId;
+	/// Signature of `signer` over `payload`
+	fn sign(signer: &AccountId, payload: &[u8]) -> Signature;
+}
+
+/// Identifier of a balance snapshot, ids start from 1
+pub type SnapshotId = u32;
+
+/// Number of the indices below `count` for which `before` holds, found by binary search.
+/// `before` has to hold for a prefix of the indices, as for entries sorted by a key
+/// compared to a bound
+pub(crate) fn partition_point(count: u32, before: impl Fn(u32) -> bool) -> u32 {
+	let (mut low, mut high) = (0, count);
+	while low < high {
+		let mid = low + (high - low) / 2;
+		if before(mid) {
+			low = mid + 1;
+		} else {
+			high = mid;
+		}
+	}
+	low
+}
+
+/// Domain tag prepended to every signed permit payload
+pub const PERMIT_DOMAIN: &[u8] = b"erc20/permit";
+
+/// Domain tag prepended to every signed delegation payload
+pub const DELEGATION_DOMAIN: &[u8] = b"erc20/delegate";
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::{
-		dispatch::{DispatchResultWithPostInfo, Vec},
+		dispatch::{DispatchResult, DispatchResultWithPostInfo, Vec},
 		pallet_prelude::*,
+		traits::{BalanceStatus, ExistenceRequirement, LockIdentifier, PalletInfo, WithdrawReasons},
+		transactional,
 		sp_runtime::{
 			traits::{
 				AtLeast32BitUnsigned, CheckedAdd, CheckedSub,
-				MaybeSerializeDeserialize, Bounded,
+				MaybeSerializeDeserialize, Bounded, One, Zero, Saturating,
+				IdentifyAccount, Verify, Convert,
 			},
 		}
 	};
@@ -31,103 +93,213 @@ pub mod pallet {
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
-	pub struct Pallet<T>(_);
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
+	/// Genesis config describes the token registered under the first asset id
 	#[pallet::genesis_config]
-	pub struct GenesisConfig<T: Config> {
+	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
+		pub phantom: PhantomData<I>,
 		pub balances: Vec<(T::AccountId, T::Balance)>,
 		pub name: Vec<u8>,
 		pub sym: Vec<u8>,
 		pub decimals: u8,
+		pub cap: Option<T::Balance>,
+		/// (account, starting block, cliff, per block release, locked amount)
+		#[allow(clippy::type_complexity)]
+		pub vesting: Vec<(T::AccountId, T::BlockNumber, T::BlockNumber, T::Balance, T::Balance)>,
 	}
 
 	#[cfg(feature = "std")]
-	impl<T: Config> GenesisConfig<T> {
+	impl<T: Config<I>, I: 'static> GenesisConfig<T, I> {
 		pub fn build_storage(&self) -> Result<sp_runtime::Storage, String> {
-			<Self as GenesisBuild<T>>::build_storage(self)
+			<Self as GenesisBuild<T, I>>::build_storage(self)
 		}
 
 		pub fn assimilate_storage(&self, storage: &mut sp_runtime::Storage) -> Result<(), String> {
-			<Self as GenesisBuild<T>>::assimilate_storage(self, storage)
+			<Self as GenesisBuild<T, I>>::assimilate_storage(self, storage)
 		}
 	}
 
 	#[cfg(feature = "std")]
-	impl<T: Config> Default for GenesisConfig<T> {
+	impl<T: Config<I>, I: 'static> Default for GenesisConfig<T, I> {
 		fn default() -> Self {
 			Self { 
+				phantom: Default::default(),
 				balances: Default::default(),
 				name: Vec::new(),
 				sym: Vec::new(),
-				decimals: 0
+				decimals: DEFAULT_DECIMALS,
+				cap: None,
+				vesting: Default::default(),
 			}
 		}
 	}
 
 	#[pallet::genesis_build]
-	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+	impl<T: Config<I>, I: 'static> GenesisBuild<T, I> for GenesisConfig<T, I> {
 		fn build(&self) {
+			let asset_id = T::AssetId::zero();
+			assert!(
+				Pallet::<T, I>::ensure_metadata_bounds(&self.name, &self.sym).is_ok(),
+				"Genesis build failed: name or symbol exceeds StringLimit"
+			);
 			let total_supply = self.balances
 							.iter()
 							.map(|(_, y)| *y)
 							.fold(T::Balance::default(),|x, y| {
 								x.checked_add(&y).expect("Genesis build failed: Total supply overflow")
 							});
+			if let Some(cap) = self.cap {
+				assert!(total_supply <= cap, "Genesis build failed: Total supply exceeds cap");
+				Cap::<T, I>::insert(asset_id, cap);
+			}
 
-			TotalSupply::<T>::mutate(|x| *x = total_supply);
-			Name::<T>::mutate(|x| *x = self.name.clone());
-			Symbol::<T>::mutate(|x| *x = self.sym.clone());
+			TotalSupply::<T, I>::insert(asset_id, total_supply);
+			Name::<T, I>::insert(asset_id, self.name.clone());
+			Symbol::<T, I>::insert(asset_id, self.sym.clone());
+			Decimals::<T, I>::insert(asset_id, self.decimals);
+			NextAssetId::<T, I>::put(T::AssetId::one());
 
 			for (acc, bal) in &self.balances {
-				BalanceOf::<T>::insert(acc, bal);
+				if bal.is_zero() {
+					continue;
+				}
+				assert!(*bal >= T::MinimumBalance::get(), "Genesis build failed: balance below MinimumBalance");
+				BalanceOf::<T, I>::insert(asset_id, acc, bal);
+			}
+
+			for &(ref who, starting_block, cliff, per_block, locked) in &self.vesting {
+				let schedule = VestingInfo { locked, per_block, starting_block, cliff };
+				assert!(schedule.is_valid(), "Genesis build failed: invalid vesting schedule");
+				assert!(
+					locked <= BalanceOf::<T, I>::get(asset_id, who),
+					"Genesis build failed: vesting locks more than the account balance"
+				);
+				VestingSchedules::<T, I>::insert(asset_id, who, schedule);
 			}
 		}
 	}
 
 	/// The module configuration trait.
 	#[pallet::config]
-	pub trait Config: 
+	pub trait Config<I: 'static = ()>: 
 		frame_system::Config +
 	{
-		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		type Event: From<Event<Self, I>> + IsType<<Self as frame_system::Config>::Event>;
 		type Balance: Parameter + Member + AtLeast32BitUnsigned + Codec + Default + Copy +
 					MaybeSerializeDeserialize + Debug;
+		/// Identifier of a token in the registry
+		type AssetId: Parameter + Member + AtLeast32BitUnsigned + Codec + Default + Copy +
+					MaybeSerializeDeserialize + Debug;
+		/// Origin allowed to register new tokens
+		type CreateOrigin: EnsureOrigin<Self::Origin>;
+		/// Origin allowed to mint tokens to any account
+		type MintOrigin: EnsureOrigin<Self::Origin>;
+		/// Origin allowed to destroy tokens of any account
+		type BurnOrigin: EnsureOrigin<Self::Origin>;
+		/// Origin allowed to change name, symbol and decimals of tokens
+		type MetadataOrigin: EnsureOrigin<Self::Origin>;
+		/// Maximum length of token name and symbol
+		#[pallet::constant]
+		type StringLimit: Get<u32>;
+		/// Converts block numbers of vesting schedules to released amounts
+		type BlockNumberToBalance: Convert<Self::BlockNumber, Self::Balance>;
+		/// Smallest amount vested_transfer moves, so the single schedule of an account can't
+		/// be taken by a dust transfer
+		#[pallet::constant]
+		type MinVestedTransfer: Get<Self::Balance>;
+		/// Smallest non zero balance an account can hold, smaller remainders are removed as dust
+		#[pallet::constant]
+		type MinimumBalance: Get<Self::Balance>;
+		/// Handler of dust removed from accounts
+		type DustRemoval: OnDust<Self::AssetId, Self::AccountId, Self::Balance>;
+		/// Maximum number of locks an account is expected to have, not enforced
+		#[pallet::constant]
+		type MaxLocks: Get<u32>;
+		/// Maximum number of transfers in a single batch
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
+		/// Maximum number of allowances removed by a single revoke_all_allowances
+		#[pallet::constant]
+		type MaxSpenders: Get<u32>;
+		/// Whether approve refuses to change a non-zero allowance to another non-zero value,
+		/// such changes then go through approve_checked or through zero
+		#[pallet::constant]
+		type StrictApprove: Get<bool>;
+		/// Whether transfers to the sender itself fail with SelfTransfer
+		#[pallet::constant]
+		type ForbidSelfTransfer: Get<bool>;
+		/// Whether transfers of zero tokens fail with ZeroAmount
+		#[pallet::constant]
+		type ForbidZeroAmount: Get<bool>;
+		/// Off-chain signature accepted by permit
+		type Signature: Parameter + Verify<Signer = Self::Signer>;
+		/// Public key of the permit signer, identifies the token owner
+		type Signer: IdentifyAccount<AccountId = Self::AccountId>;
+		/// Signs the payloads of permit and delegate_by_sig benchmarks
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::AccountId, Self::Signature>;
+		/// Origin allowed to pause tokens and freeze accounts
+		type PauseOrigin: EnsureOrigin<Self::Origin>;
+		/// Origin allowed to change the supply cap of a token
+		type CapOrigin: EnsureOrigin<Self::Origin>;
+		/// Origin allowed to take balance snapshots
+		type SnapshotOrigin: EnsureOrigin<Self::Origin>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {}
 
 
 	#[pallet::type_value]
 	pub fn DefaultDecimals() -> u8 { DEFAULT_DECIMALS }
 
 	// pallet storages:
+	#[pallet::storage]
+	#[pallet::getter(fn get_next_asset_id)]
+	/// Asset id to be allocated for the next created token
+	pub type NextAssetId<T: Config<I>, I: 'static = ()> = StorageValue<_, T::AssetId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_owner)]
+	/// Account that created a token
+	pub type Owner<T: Config<I>, I: 'static = ()> = StorageMap<_, Blake2_128Concat, T::AssetId, T::AccountId, OptionQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn get_total_supply)]
 	/// Total supply
-	pub(super) type TotalSupply<T: Config> = StorageValue<_, T::Balance, ValueQuery>;
+	pub(super) type TotalSupply<T: Config<I>, I: 'static = ()> = StorageMap<_, Blake2_128Concat, T::AssetId, T::Balance, ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_name)]
 	/// Name byte vector
-	pub type Name<T: Config> = StorageValue<_, Vec<u8>, ValueQuery>;
+	pub type Name<T: Config<I>, I: 'static = ()> = StorageMap<_, Blake2_128Concat, T::AssetId, Vec<u8>, ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_symbol)]
 	/// Symbol byte vector
-	pub type Symbol<T: Config> = StorageValue<_, Vec<u8>, ValueQuery>;
+	pub type Symbol<T: Config<I>, I: 'static = ()> = StorageMap<_, Blake2_128Concat, T::AssetId, Vec<u8>, ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_decimals)]
 	/// Decimals
-	pub type Decimals<T: Config> = StorageValue<_, u8, ValueQuery, DefaultDecimals>;
+	pub type Decimals<T: Config<I>, I: 'static = ()> = StorageMap<_, Blake2_128Concat, T::AssetId, u8, ValueQuery, DefaultDecimals>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_cap)]
+	/// Maximum total supply, unbounded if not set
+	pub type Cap<T: Config<I>, I: 'static = ()> = StorageMap<_, Blake2_128Concat, T::AssetId, T::Balance, OptionQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_balance)]
 	/// Balance of an account
-	pub(super) type BalanceOf<T: Config> = StorageMap<
+	pub(super) type BalanceOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
 		T::AccountId,
 		T::Balance,
 		ValueQuery
@@ -135,20 +307,188 @@ pub mod pallet {
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_allowance)]
-	/// Allowance of an account given to another account
-	pub(super) type AllowanceOf<T: Config> = StorageDoubleMap<
+	/// Allowance of an account given to another account, keyed by (asset id, owner) and spender
+	pub(super) type AllowanceOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(T::AssetId, T::AccountId),
+		Blake2_128Concat,
+		T::AccountId,
+		T::Balance,
+		ValueQuery
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_allowance_expiry)]
+	/// Last block an allowance can be spent at, allowances without an entry never expire
+	pub type AllowanceExpiry<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(T::AssetId, T::AccountId),
+		Blake2_128Concat,
+		T::AccountId,
+		T::BlockNumber,
+		OptionQuery
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_permit_nonce)]
+	/// Nonce of the next permit or delegation signed by an account
+	pub type PermitNonces<T: Config<I>, I: 'static = ()> = StorageMap<_, Blake2_128Concat, T::AccountId, T::Index, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn is_paused)]
+	/// Whether all transfers of a token are halted
+	pub type Paused<T: Config<I>, I: 'static = ()> = StorageMap<_, Blake2_128Concat, T::AssetId, bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn is_frozen)]
+	/// Whether an account can not move its tokens
+	pub type Frozen<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		bool,
+		ValueQuery
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_current_snapshot_id)]
+	/// Id of the latest snapshot of a token, zero if no snapshot was taken
+	pub type CurrentSnapshotId<T: Config<I>, I: 'static = ()> = StorageMap<_, Blake2_128Concat, T::AssetId, SnapshotId, ValueQuery>;
+
+	#[pallet::storage]
+	/// Number of balance checkpoints recorded for an account
+	pub(super) type NumAccountSnapshots<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		u32,
+		ValueQuery
+	>;
+
+	#[pallet::storage]
+	/// Balances of an account recorded before the first change after each snapshot,
+	/// indexed from zero in snapshot id order
+	pub(super) type AccountSnapshots<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		(T::AccountId, u32),
+		(SnapshotId, T::Balance),
+		OptionQuery
+	>;
+
+	#[pallet::storage]
+	/// Number of total supply checkpoints recorded for a token
+	pub(super) type NumTotalSupplySnapshots<T: Config<I>, I: 'static = ()> = StorageMap<
 		_,
 		Blake2_128Concat,
+		T::AssetId,
+		u32,
+		ValueQuery
+	>;
+
+	#[pallet::storage]
+	/// Total supply recorded before the first change after each snapshot,
+	/// indexed from zero in snapshot id order
+	pub(super) type TotalSupplySnapshots<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		u32,
+		(SnapshotId, T::Balance),
+		OptionQuery
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_delegate)]
+	/// Account the voting power of a holder is delegated to
+	pub type Delegates<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		T::AccountId,
+		OptionQuery
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_num_checkpoints)]
+	/// Number of voting power checkpoints of a delegatee
+	pub type NumCheckpoints<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		u32,
+		ValueQuery
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_checkpoint)]
+	/// Voting power of a delegatee at the blocks it changed, indexed from zero in block order.
+	/// Each checkpoint is a separate entry, so recording one does not decode the others
+	pub type Checkpoints<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		(T::AccountId, u32),
+		(T::BlockNumber, T::Balance),
+		OptionQuery
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_vesting)]
+	/// Vesting schedule locking part of an account balance
+	pub type VestingSchedules<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
 		T::AccountId,
+		VestingInfo<T::Balance, T::BlockNumber>,
+		OptionQuery
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_reserved_balance)]
+	/// Balance set aside by other pallets, it can not be transferred but counts to the total supply
+	pub type ReservedBalance<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
 		Blake2_128Concat,
 		T::AccountId,
 		T::Balance,
 		ValueQuery
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn get_locks)]
+	/// Named locks on the free balance of an account
+	pub type Locks<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		Vec<BalanceLock<T::Balance>>,
+		ValueQuery
+	>;
+
 	// Pallet Errors
 	#[pallet::error]
-	pub enum Error<T> {
+	pub enum Error<T, I = ()> {
 		/// Error for if transfer amount exceeds balance
 		TransferAmountExceedsBalance,
 		/// Decreases allowance below zero error
@@ -159,29 +499,162 @@ pub mod pallet {
 		InsufficientAllowance,
 		/// Burn amount exceeds balacnde error
 		BurnAmountExceedsBalance,
+		/// Token with given asset id is not registered
+		UnknownAsset,
+		/// Asset ids are exhausted
+		NoAvailableAssetId,
+		/// Name or symbol is longer than StringLimit
+		BadMetadata,
+		/// Permit deadline block has passed
+		PermitExpired,
+		/// Permit is not signed by the token owner
+		BadPermitSignature,
+		/// Token transfers are paused
+		TokenPaused,
+		/// Account is frozen
+		AccountFrozen,
+		/// Total supply would exceed the cap
+		CapExceeded,
+		/// No snapshot id is left
+		NoAvailableSnapshotId,
+		/// Signed delegation is past its expiry block
+		DelegationExpired,
+		/// Delegation is not signed by the delegator
+		BadDelegationSignature,
+		/// Batch has more transfers than MaxBatchSize
+		BatchTooLarge,
+		/// Transfer would move tokens locked by a vesting schedule
+		LockedByVesting,
+		/// Account has no vesting schedule
+		NotVesting,
+		/// Account already has a vesting schedule
+		ExistingVestingSchedule,
+		/// Vesting schedule locks nothing or never releases
+		InvalidVestingSchedule,
+		/// Free balance is lower than the amount to reserve or withdraw
+		InsufficientBalance,
+		/// Free balance is locked
+		LiquidityRestrictions,
+		/// Resulting balance would be below MinimumBalance
+		ExistentialDeposit,
+		/// Transfer would leave the sender below MinimumBalance
+		KeepAlive,
+		/// Allowance expiry block has passed
+		AllowanceExpired,
+		/// Stored allowance differs from the expected one
+		AllowanceMismatch,
+		/// Non-zero allowance can't be changed to another non-zero value by approve
+		UnsafeAllowanceChange,
+		/// Account encoded as all zero bytes can't send or receive tokens
+		SentinelAccount,
+		/// Error for balance underflow
+		BalanceUnderflow,
+		/// Sender and recipient are the same account, forbidden by ForbidSelfTransfer
+		SelfTransfer,
+		/// Transfer of zero tokens, forbidden by ForbidZeroAmount
+		ZeroAmount,
+		/// Vested transfer amount is below MinVestedTransfer
+		AmountLow,
+		/// Account holds no tokens, only existing accounts can be deposited into
+		DeadAccount,
 	}
 
 	// Pallet events
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	#[pallet::metadata(T::AccountId = "AccountId", T::Balance = "Balance")]
-	pub enum Event<T: Config> {
-		/// \[From, To, Amount\]
-		Transfer(T::AccountId, T::AccountId, T::Balance),
-		/// \[From, To, Amount\]
-		Approval(T::AccountId, T::AccountId, T::Balance),
+	#[pallet::metadata(
+		T::AccountId = "AccountId",
+		T::Balance = "Balance",
+		T::AssetId = "AssetId",
+		T::BlockNumber = "BlockNumber"
+	)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// Tokens moved between accounts, balances are the ones after the transfer
+		Transfer {
+			asset_id: T::AssetId,
+			from: T::AccountId,
+			to: T::AccountId,
+			amount: T::Balance,
+			from_balance: T::Balance,
+			to_balance: T::Balance,
+		},
+		/// Allowance of spender over the owner's tokens was set
+		Approval { asset_id: T::AssetId, owner: T::AccountId, spender: T::AccountId, amount: T::Balance },
+		/// Allowance that can't be spent after `expires_at` was set
+		ApprovalWithExpiry {
+			asset_id: T::AssetId,
+			owner: T::AccountId,
+			spender: T::AccountId,
+			amount: T::Balance,
+			expires_at: T::BlockNumber,
+		},
+		/// Token was registered
+		TokenCreated { asset_id: T::AssetId, owner: T::AccountId },
+		/// Token metadata was changed
+		MetadataSet { asset_id: T::AssetId, name: Vec<u8>, symbol: Vec<u8>, decimals: u8 },
+		/// Transfers of the token were halted
+		Paused { asset_id: T::AssetId },
+		/// Transfers of the token were resumed
+		Unpaused { asset_id: T::AssetId },
+		/// Account can't move the token anymore
+		Frozen { asset_id: T::AssetId, who: T::AccountId },
+		/// Frozen account can move the token again
+		Thawed { asset_id: T::AssetId, who: T::AccountId },
+		/// Supply cap was changed, `None` removes it
+		CapSet { asset_id: T::AssetId, cap: Option<T::Balance> },
+		/// Balance snapshot was taken
+		Snapshot { asset_id: T::AssetId, snapshot_id: SnapshotId },
+		/// Delegator moved its votes to another delegate
+		DelegateChanged {
+			asset_id: T::AssetId,
+			delegator: T::AccountId,
+			from_delegate: Option<T::AccountId>,
+			to_delegate: T::AccountId,
+		},
+		/// Votes of a delegate changed
+		DelegateVotesChanged {
+			asset_id: T::AssetId,
+			delegate: T::AccountId,
+			previous_votes: T::Balance,
+			new_votes: T::Balance,
+		},
+		/// Vesting schedule locking `locked` tokens was added
+		VestingCreated { asset_id: T::AssetId, who: T::AccountId, locked: T::Balance },
+		/// Part of the vested tokens was released
+		VestingUpdated { asset_id: T::AssetId, who: T::AccountId, still_locked: T::Balance },
+		/// All vested tokens were released
+		VestingCompleted { asset_id: T::AssetId, who: T::AccountId },
+		/// Free tokens were reserved
+		Reserved { asset_id: T::AssetId, who: T::AccountId, amount: T::Balance },
+		/// Reserved tokens were made free
+		Unreserved { asset_id: T::AssetId, who: T::AccountId, amount: T::Balance },
+		/// Reserved tokens were moved to another account
+		ReserveRepatriated {
+			asset_id: T::AssetId,
+			from: T::AccountId,
+			to: T::AccountId,
+			amount: T::Balance,
+			status: BalanceStatus,
+		},
+		/// Balance below MinimumBalance was removed from an account
+		DustLost { asset_id: T::AssetId, who: T::AccountId, amount: T::Balance },
+		/// Tokens were created
+		Minted { asset_id: T::AssetId, to: T::AccountId, amount: T::Balance },
+		/// Tokens were destroyed
+		Burned { asset_id: T::AssetId, from: T::AccountId, amount: T::Balance },
 	}
 
 	#[deprecated(note = "use `Event` instead")]
-	pub type RawEvent<T> = Event<T>;
+	pub type RawEvent<T, I = ()> = Event<T, I>;
 
 	/// Calls:
 	#[pallet::call]
-	impl<T: Config> Pallet<T> {
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		/// <pre>
-		/// Method: transfer(origin: OriginFor<T>, to: T::AccountId, amount: T::Balance)
+		/// Method: transfer(origin: OriginFor<T>, asset_id: T::AssetId, to: T::AccountId, amount: T::Balance)
 		/// 
 		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
 		///            to: T::AccountId - Account to send to
 		///            amount: T::Balance - amount of tokens
 		/// 
@@ -189,17 +662,23 @@ pub mod pallet {
 		///
 		/// Sets amount as the allowance of spender over the caller’s tokens
 		/// </pre>
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2))]
-		pub fn transfer(origin: OriginFor<T>, to: T::AccountId, amount: T::Balance) -> DispatchResultWithPostInfo {
+		#[pallet::weight(T::WeightInfo::transfer())]
+		pub fn transfer(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			to: T::AccountId,
+			amount: T::Balance
+		) -> DispatchResultWithPostInfo {
 			let from = ensure_signed(origin)?;
-			Self::_transfer(from, to, amount)?;
+			Self::_transfer(asset_id, from, to, amount)?;
 			Ok(().into())
 		}
 
 		/// <pre>
-		/// Method: approve(origin: OriginFor<T>, spender: T::AccountId, amount: T::Balance)
+		/// Method: approve(origin: OriginFor<T>, asset_id: T::AssetId, spender: T::AccountId, amount: T::Balance)
 		/// 
 		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
 		///            spender: T::AccountId - 
 		///            amount: T::Balance - amount of tokens
 		/// 
@@ -207,20 +686,104 @@ pub mod pallet {
 		///
 		/// Sets amount as the allowance of spender over the caller’s tokens
 		/// </pre>
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
-		pub fn approve(origin: OriginFor<T>, spender: T::AccountId, amount: T::Balance) -> DispatchResultWithPostInfo {
+		#[pallet::weight(T::WeightInfo::approve())]
+		pub fn approve(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			spender: T::AccountId,
+			amount: T::Balance
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			Self::ensure_asset_exists(asset_id)?;
+			if T::StrictApprove::get() && !amount.is_zero() {
+				ensure!(
+					AllowanceOf::<T, I>::get((asset_id, &owner), &spender).is_zero(),
+					Error::<T, I>::UnsafeAllowanceChange
+				);
+			}
+			Self::_approve_with_expiry(asset_id, owner, spender, amount, None)?;
+			Ok(().into())
+		}
+
+		/// <pre>
+		/// Method: approve_checked(origin: OriginFor<T>,
+		///							asset_id: T::AssetId,
+		///							spender: T::AccountId,
+		///							expected_current: T::Balance,
+		///							new_amount: T::Balance)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		///            spender: T::AccountId - Account to approve
+		///            expected_current: T::Balance - allowance the caller expects to replace
+		///            new_amount: T::Balance - amount of tokens
+		/// 
+		/// Access: Token holder
+		///
+		/// Sets new_amount as the allowance of spender over the caller’s tokens
+		/// if the current allowance is expected_current, so a spender can't
+		/// spend both the old and the new allowance
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::approve_checked())]
+		pub fn approve_checked(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			spender: T::AccountId,
+			expected_current: T::Balance,
+			new_amount: T::Balance
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			Self::ensure_asset_exists(asset_id)?;
+			ensure!(
+				AllowanceOf::<T, I>::get((asset_id, &owner), &spender) == expected_current,
+				Error::<T, I>::AllowanceMismatch
+			);
+			Self::_approve_with_expiry(asset_id, owner, spender, new_amount, None)?;
+			Ok(().into())
+		}
+
+		/// <pre>
+		/// Method: approve_with_expiry(origin: OriginFor<T>,
+		///							asset_id: T::AssetId,
+		///							spender: T::AccountId,
+		///							amount: T::Balance,
+		///							expires_at: T::BlockNumber)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		///            spender: T::AccountId - Account to approve
+		///            amount: T::Balance - amount of tokens
+		///            expires_at: T::BlockNumber - last block the allowance can be spent at
+		/// 
+		/// Access: Token holder
+		///
+		/// Sets amount as the allowance of spender over the caller’s tokens,
+		/// the allowance can't be spent after expires_at
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::approve_with_expiry())]
+		pub fn approve_with_expiry(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			spender: T::AccountId,
+			amount: T::Balance,
+			expires_at: T::BlockNumber
+		) -> DispatchResultWithPostInfo {
 			let owner = ensure_signed(origin)?;
-			Self::_approve(owner, spender, amount)?;
+			Self::ensure_asset_exists(asset_id)?;
+			ensure!(frame_system::Module::<T>::block_number() <= expires_at, Error::<T, I>::AllowanceExpired);
+			Self::_approve_with_expiry(asset_id, owner, spender, amount, Some(expires_at))?;
 			Ok(().into())
 		}
 
 		/// <pre>
-		/// Method: transfer_from(origin: OriginFor<T>, 
+		/// Method: transfer_from(origin: OriginFor<T>,
+		///							asset_id: T::AssetId,
 		///							from: T::AccountId,
-		///							to: T::AccountId, 
+		///							to: T::AccountId,
 		///							amount: T::Balance)
 		/// 
 		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
 		///            from: T::AccountId - Account to send to
 		///            to: T::Balance - amount of tokens
 		///            amount: T::Balance - amount of tokens
@@ -229,24 +792,140 @@ pub mod pallet {
 		///
 		/// Moves amount tokens from sender to recipient using the allowance mechanism.
 		/// amount is then deducted from the caller’s allowance.
+		/// The allowance is left unchanged if the transfer fails
 		/// </pre>
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3))]
+		#[pallet::weight(T::WeightInfo::transfer_from())]
+		#[transactional]
 		pub fn transfer_from(
-			origin: OriginFor<T>, 
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
 			from: T::AccountId,
-			to: T::AccountId, 
+			to: T::AccountId,
+			amount: T::Balance
+		) -> DispatchResultWithPostInfo {
+			let spender = ensure_signed(origin)?;
+			Self::_spend_allowance(asset_id, from.clone(), spender, amount)?;
+			Self::_transfer(asset_id, from, to, amount)?;
+			Ok(().into())
+		}
+
+		/// <pre>
+		/// Method: transfer_all(origin: OriginFor<T>, asset_id: T::AssetId, to: T::AccountId)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		///            to: T::AccountId - Account to send to
+		/// 
+		/// Access: Any account
+		///
+		/// Moves all transferable tokens of the caller to recipient,
+		/// tokens held by locks and vesting stay on the caller account
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::transfer_all())]
+		pub fn transfer_all(origin: OriginFor<T>, asset_id: T::AssetId, to: T::AccountId) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let amount = Self::reducible_balance(asset_id, &sender, false);
+			Self::_transfer(asset_id, sender, to, amount)?;
+			Ok(().into())
+		}
+
+		/// <pre>
+		/// Method: transfer_keep_alive(origin: OriginFor<T>,
+		///							asset_id: T::AssetId,
+		///							to: T::AccountId,
+		///							amount: T::Balance)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		///            to: T::AccountId - Account to send to
+		///            amount: T::Balance - amount of tokens
+		/// 
+		/// Access: Any account
+		///
+		/// Moves amount tokens from the caller to recipient,
+		/// fails if the caller would be left with less than MinimumBalance
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::transfer_keep_alive())]
+		pub fn transfer_keep_alive(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			to: T::AccountId,
 			amount: T::Balance
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::_transfer_with_existence(asset_id, sender, to, amount, ExistenceRequirement::KeepAlive)?;
+			Ok(().into())
+		}
+
+		/// <pre>
+		/// Method: transfer_batch(origin: OriginFor<T>,
+		///							asset_id: T::AssetId,
+		///							transfers: Vec<(T::AccountId, T::Balance)>)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		///            transfers: Vec<(T::AccountId, T::Balance)> - recipients and amounts
+		/// 
+		/// Access: Any account
+		///
+		/// Moves tokens from caller to every recipient, either all transfers succeed or none
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::transfer_batch(transfers.len() as u32))]
+		#[transactional]
+		pub fn transfer_batch(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			transfers: Vec<(T::AccountId, T::Balance)>
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			ensure!(transfers.len() <= T::MaxBatchSize::get() as usize, Error::<T, I>::BatchTooLarge);
+			for (to, amount) in transfers {
+				Self::_transfer(asset_id, sender.clone(), to, amount)?;
+			}
+			Ok(().into())
+		}
+
+		/// <pre>
+		/// Method: transfer_from_batch(origin: OriginFor<T>,
+		///							asset_id: T::AssetId,
+		///							from: T::AccountId,
+		///							transfers: Vec<(T::AccountId, T::Balance)>)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		///            from: T::AccountId - Account to send from
+		///            transfers: Vec<(T::AccountId, T::Balance)> - recipients and amounts
+		/// 
+		/// Access: Allowed to spend tokens account
+		///
+		/// Moves tokens from sender to every recipient using the allowance mechanism,
+		/// either all transfers succeed or none
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::transfer_from_batch(transfers.len() as u32))]
+		#[transactional]
+		pub fn transfer_from_batch(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			from: T::AccountId,
+			transfers: Vec<(T::AccountId, T::Balance)>
 		) -> DispatchResultWithPostInfo {
 			let spender = ensure_signed(origin)?;
-			Self::_spend_allowance(from.clone(), spender, amount)?;
-			Self::_transfer(from, to, amount)?;
+			ensure!(transfers.len() <= T::MaxBatchSize::get() as usize, Error::<T, I>::BatchTooLarge);
+			for (to, amount) in transfers {
+				Self::_spend_allowance(asset_id, from.clone(), spender.clone(), amount)?;
+				Self::_transfer(asset_id, from.clone(), to, amount)?;
+			}
 			Ok(().into())
 		}
 
 		/// <pre>
-		/// Method: increase_allowance(origin: OriginFor<T>, sender: T::AccountId, added_value: T::Balance)
+		/// Method: increase_allowance(origin: OriginFor<T>,
+		///							asset_id: T::AssetId,
+		///							sender: T::AccountId,
+		///							added_value: T::Balance)
 		/// 
 		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
 		///            sender: T::AccountId - Account to increase its allowance
 		///            added_value: T::Balance - amount of tokens
 		/// 
@@ -254,21 +933,31 @@ pub mod pallet {
 		///
 		/// increase allowance of account
 		/// </pre>
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
-		pub fn increase_allowance(origin: OriginFor<T>, sender: T::AccountId, added_value: T::Balance) -> DispatchResultWithPostInfo {
+		#[pallet::weight(T::WeightInfo::increase_allowance())]
+		pub fn increase_allowance(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			sender: T::AccountId,
+			added_value: T::Balance
+		) -> DispatchResultWithPostInfo {
 			let owner = ensure_signed(origin)?;
-			let amount = AllowanceOf::<T>::get(&owner, &sender)
+			Self::ensure_asset_exists(asset_id)?;
+			let amount = AllowanceOf::<T, I>::get((asset_id, &owner), &sender)
 						.checked_add(&added_value)
-						.ok_or(Error::<T>::BalanceOverflow)?;
+						.ok_or(Error::<T, I>::BalanceOverflow)?;
 
-			Self::_approve(owner, sender, amount)?;
+			Self::_approve(asset_id, owner, sender, amount)?;
 			Ok(().into())
 		}
 
 		/// <pre>
-		/// Method: decrease_allowance(origin: OriginFor<T>, sender: T::AccountId, substracted_value: T::Balance)
+		/// Method: decrease_allowance(origin: OriginFor<T>,
+		///							asset_id: T::AssetId,
+		///							sender: T::AccountId,
+		///							substracted_value: T::Balance)
 		/// 
 		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
 		///            sender: T::AccountId - Account to send to
 		///            substracted_value: T::Balance - amount of tokens
 		/// 
@@ -276,83 +965,1190 @@ pub mod pallet {
 		///
 		/// Decreases allowance of account
 		/// </pre>
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
-		pub fn decrease_allowance(origin: OriginFor<T>, sender: T::AccountId, substracted_value: T::Balance) -> DispatchResultWithPostInfo {
+		#[pallet::weight(T::WeightInfo::decrease_allowance())]
+		pub fn decrease_allowance(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			sender: T::AccountId,
+			substracted_value: T::Balance
+		) -> DispatchResultWithPostInfo {
 			let owner = ensure_signed(origin)?;
-			let current_allowance = AllowanceOf::<T>::get(&owner, &sender);
-			ensure!(current_allowance >= substracted_value, Error::<T>::DecreasedAllowanceBelowZero);
+			Self::ensure_asset_exists(asset_id)?;
+			let current_allowance = AllowanceOf::<T, I>::get((asset_id, &owner), &sender);
+			ensure!(current_allowance >= substracted_value, Error::<T, I>::DecreasedAllowanceBelowZero);
 			let amount = current_allowance.checked_sub(&substracted_value)
-						.ok_or(Error::<T>::BalanceOverflow)?;
+						.ok_or(Error::<T, I>::BalanceUnderflow)?;
 
-			Self::_approve(owner, sender, amount)?;
+			Self::_approve(asset_id, owner, sender, amount)?;
 			Ok(().into())
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		/// Transfers tokens from account to another account
-		pub fn _transfer(from: T::AccountId, to: T::AccountId, amount: T::Balance) -> DispatchResultWithPostInfo {			
-			BalanceOf::<T>::try_mutate(&from, |from_bal| -> DispatchResultWithPostInfo {
-				ensure!(*from_bal >= amount, Error::<T>::TransferAmountExceedsBalance);
-				BalanceOf::<T>::try_mutate(&to, |to_bal| -> DispatchResultWithPostInfo {
-					*from_bal = from_bal.checked_sub(&amount).ok_or(Error::<T>::BalanceOverflow)?;
-					*to_bal = to_bal.checked_add(&amount).ok_or(Error::<T>::BalanceOverflow)?;
-					Ok(().into())
-				})?;
-				Ok(().into())
-			})?;
-			Self::deposit_event(Event::Transfer(from, to, amount));
+		/// <pre>
+		/// Method: revoke_allowance(origin: OriginFor<T>, asset_id: T::AssetId, spender: T::AccountId)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		///            spender: T::AccountId - Account to revoke the allowance of
+		/// 
+		/// Access: Token holder
+		///
+		/// Removes the allowance of spender over the caller’s tokens
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::revoke_allowance())]
+		pub fn revoke_allowance(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			spender: T::AccountId
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			Self::ensure_asset_exists(asset_id)?;
+			Self::_revoke_allowance(asset_id, owner, spender);
 			Ok(().into())
 		}
 
-		/// Sets `amount` as the allowance of `spender` over the `owner` s tokens.
-		pub fn _approve(owner: T::AccountId, spender: T::AccountId, amount: T::Balance) -> DispatchResultWithPostInfo {
-			AllowanceOf::<T>::try_mutate(&owner, &spender, |bal| -> DispatchResultWithPostInfo {
-				*bal = amount;
-				Ok(().into())
-			})?;
-			Self::deposit_event(Event::Approval(owner, spender, amount));
-			Ok(().into())
+		/// <pre>
+		/// Method: revoke_all_allowances(origin: OriginFor<T>, asset_id: T::AssetId)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		/// 
+		/// Access: Token holder
+		///
+		/// Removes up to MaxSpenders allowances over the caller’s tokens,
+		/// repeat the call while allowances remain
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::revoke_all_allowances(T::MaxSpenders::get()))]
+		pub fn revoke_all_allowances(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			Self::ensure_asset_exists(asset_id)?;
+			let spenders: Vec<T::AccountId> = AllowanceOf::<T, I>::iter_prefix((asset_id, &owner))
+				.map(|(spender, _)| spender)
+				.take(T::MaxSpenders::get() as usize)
+				.collect();
+			let revoked = spenders.len() as u32;
+			for spender in spenders {
+				Self::_revoke_allowance(asset_id, owner.clone(), spender);
+			}
+			Ok(Some(T::WeightInfo::revoke_all_allowances(revoked)).into())
 		}
 
-		/// Updates `owner` s allowance for `spender` based on spent `amount`
-		pub fn _spend_allowance(owner: T::AccountId, spender: T::AccountId, amount: T::Balance) -> DispatchResultWithPostInfo {
-			let current_allowance = AllowanceOf::<T>::get(&owner, &spender);
-			if current_allowance != T::Balance::max_value() {
-				let new_allowance = current_allowance.checked_sub(&amount)
-									.ok_or( Error::<T>::InsufficientAllowance)?;
-				Self::_approve(owner, spender, new_allowance)?;
-			}
+		/// <pre>
+		/// Method: mint(origin: OriginFor<T>, asset_id: T::AssetId, to: T::AccountId, amount: T::Balance)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		///            to: T::AccountId - Account to receive created tokens
+		///            amount: T::Balance - amount of tokens
+		/// 
+		/// Access: MintOrigin
+		///
+		/// Creates amount tokens and assigns them to account, increasing the total supply
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::mint())]
+		pub fn mint(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			to: T::AccountId,
+			amount: T::Balance
+		) -> DispatchResultWithPostInfo {
+			T::MintOrigin::ensure_origin(origin)?;
+			Self::_mint(asset_id, to, amount)?;
 			Ok(().into())
 		}
 
-		/// Creates `amount` tokens and assigns them to `account`, increasing the total supply
-		pub fn _mint(account: T::AccountId, amount: T::Balance) -> DispatchResultWithPostInfo {
-			BalanceOf::<T>::try_mutate(&account, |balance| -> DispatchResultWithPostInfo {
-				TotalSupply::<T>::try_mutate(|bal| -> DispatchResultWithPostInfo {
-					*bal = bal.checked_add(&amount).ok_or(Error::<T>::BalanceOverflow)?;
-					Ok(().into())
-				})?;
-				*balance = balance.checked_add(&amount).ok_or(Error::<T>::BalanceOverflow)?;
-				Ok(().into())
-			})?;
-			Self::deposit_event(Event::Transfer(T::AccountId::default(), account, amount));
+		/// <pre>
+		/// Method: burn(origin: OriginFor<T>, asset_id: T::AssetId, from: T::AccountId, amount: T::Balance)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		///            from: T::AccountId - Account to destroy tokens of
+		///            amount: T::Balance - amount of tokens
+		/// 
+		/// Access: BurnOrigin
+		///
+		/// Destroys amount tokens from account, reducing the total supply
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::burn())]
+		pub fn burn(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			from: T::AccountId,
+			amount: T::Balance
+		) -> DispatchResultWithPostInfo {
+			T::BurnOrigin::ensure_origin(origin)?;
+			Self::_burn(asset_id, from, amount)?;
 			Ok(().into())
 		}
 
-		/// Destroys `amount` tokens from `account`, reducing the total supply
-		pub fn _burn(account: T::AccountId, amount: T::Balance) -> DispatchResultWithPostInfo {
-			BalanceOf::<T>::try_mutate(&account, |balance| -> DispatchResultWithPostInfo {
-				ensure!(*balance >= amount, Error::<T>::BurnAmountExceedsBalance);
-				TotalSupply::<T>::try_mutate(|bal| -> DispatchResultWithPostInfo {
-					*bal = bal.checked_sub(&amount).ok_or(Error::<T>::BalanceOverflow)?;
-					Ok(().into())
-				})?;
-				*balance = balance.checked_sub(&amount).ok_or(Error::<T>::BalanceOverflow)?;
-				Ok(().into())
-			})?;
-			Self::deposit_event(Event::Transfer(account, T::AccountId::default(), amount));
+		/// <pre>
+		/// Method: burn_self(origin: OriginFor<T>, asset_id: T::AssetId, amount: T::Balance)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		///            amount: T::Balance - amount of tokens
+		/// 
+		/// Access: Token holder
+		///
+		/// Destroys amount of caller’s tokens, reducing the total supply
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::burn_self())]
+		pub fn burn_self(origin: OriginFor<T>, asset_id: T::AssetId, amount: T::Balance) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			Self::_burn(asset_id, owner, amount)?;
+			Ok(().into())
+		}
+
+		/// <pre>
+		/// Method: create_token(origin: OriginFor<T>,
+		///							owner: T::AccountId,
+		///							name: Vec<u8>,
+		///							symbol: Vec<u8>,
+		///							decimals: u8,
+		///							initial_supply: T::Balance)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            owner: T::AccountId - Owner of the new token
+		///            name: Vec<u8> - Token name
+		///            symbol: Vec<u8> - Token symbol
+		///            decimals: u8 - Token decimals
+		///            initial_supply: T::Balance - amount of tokens assigned to the owner
+		/// 
+		/// Access: CreateOrigin
+		///
+		/// Registers a new token under the next free asset id.
+		/// Nothing is registered if the initial supply can't be minted
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::create_token())]
+		#[transactional]
+		pub fn create_token(
+			origin: OriginFor<T>,
+			owner: T::AccountId,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8,
+			initial_supply: T::Balance
+		) -> DispatchResultWithPostInfo {
+			T::CreateOrigin::ensure_origin(origin)?;
+			Self::ensure_metadata_bounds(&name, &symbol)?;
+			let asset_id = NextAssetId::<T, I>::try_mutate(|id| -> Result<T::AssetId, DispatchError> {
+				let current_id = *id;
+				*id = id.checked_add(&One::one()).ok_or(Error::<T, I>::NoAvailableAssetId)?;
+				Ok(current_id)
+			})?;
+
+			Owner::<T, I>::insert(asset_id, &owner);
+			Name::<T, I>::insert(asset_id, name);
+			Symbol::<T, I>::insert(asset_id, symbol);
+			Decimals::<T, I>::insert(asset_id, decimals);
+			Self::deposit_event(Event::TokenCreated { asset_id, owner: owner.clone() });
+
+			if !initial_supply.is_zero() {
+				Self::_mint(asset_id, owner, initial_supply)?;
+			}
+			Ok(().into())
+		}
+
+		/// <pre>
+		/// Method: set_metadata(origin: OriginFor<T>,
+		///							asset_id: T::AssetId,
+		///							name: Vec<u8>,
+		///							symbol: Vec<u8>,
+		///							decimals: u8)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		///            name: Vec<u8> - New token name
+		///            symbol: Vec<u8> - New token symbol
+		///            decimals: u8 - New token decimals
+		/// 
+		/// Access: MetadataOrigin
+		///
+		/// Changes name, symbol and decimals of a token
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::set_metadata())]
+		pub fn set_metadata(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8
+		) -> DispatchResultWithPostInfo {
+			T::MetadataOrigin::ensure_origin(origin)?;
+			Self::ensure_asset_exists(asset_id)?;
+			Self::ensure_metadata_bounds(&name, &symbol)?;
+
+			Name::<T, I>::insert(asset_id, &name);
+			Symbol::<T, I>::insert(asset_id, &symbol);
+			Decimals::<T, I>::insert(asset_id, decimals);
+			Self::deposit_event(Event::MetadataSet { asset_id, name, symbol, decimals });
+			Ok(().into())
+		}
+
+		/// <pre>
+		/// Method: permit(origin: OriginFor<T>,
+		///							asset_id: T::AssetId,
+		///							owner: T::AccountId,
+		///							spender: T::AccountId,
+		///							value: T::Balance,
+		///							deadline: T::BlockNumber,
+		///							signature: T::Signature)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		///            owner: T::AccountId - Account that signed the permit
+		///            spender: T::AccountId - Account to approve
+		///            value: T::Balance - amount of tokens
+		///            deadline: T::BlockNumber - last block the permit is valid at
+		///            signature: T::Signature - owner signature over permit_payload
+		/// 
+		/// Access: Any account
+		///
+		/// Sets value as the allowance of spender over the owner’s tokens,
+		/// given the owner signed approval
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::permit())]
+		pub fn permit(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			owner: T::AccountId,
+			spender: T::AccountId,
+			value: T::Balance,
+			deadline: T::BlockNumber,
+			signature: T::Signature
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			ensure!(frame_system::Module::<T>::block_number() <= deadline, Error::<T, I>::PermitExpired);
+			Self::ensure_asset_exists(asset_id)?;
+
+			let nonce = PermitNonces::<T, I>::get(&owner);
+			let payload = Self::permit_payload(asset_id, &owner, &spender, value, nonce, deadline);
+			ensure!(signature.verify(&payload[..], &owner), Error::<T, I>::BadPermitSignature);
+
+			PermitNonces::<T, I>::insert(&owner, nonce + One::one());
+			Self::_approve_with_expiry(asset_id, owner, spender, value, None)?;
+			Ok(().into())
+		}
+
+		/// <pre>
+		/// Method: pause(origin: OriginFor<T>, asset_id: T::AssetId)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		/// 
+		/// Access: PauseOrigin
+		///
+		/// Halts transfers, mints and burns of a token
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::pause())]
+		pub fn pause(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			Self::ensure_asset_exists(asset_id)?;
+			Paused::<T, I>::insert(asset_id, true);
+			Self::deposit_event(Event::Paused { asset_id });
+			Ok(().into())
+		}
+
+		/// <pre>
+		/// Method: unpause(origin: OriginFor<T>, asset_id: T::AssetId)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		/// 
+		/// Access: PauseOrigin
+		///
+		/// Resumes transfers, mints and burns of a token
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::unpause())]
+		pub fn unpause(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			Self::ensure_asset_exists(asset_id)?;
+			Paused::<T, I>::remove(asset_id);
+			Self::deposit_event(Event::Unpaused { asset_id });
+			Ok(().into())
+		}
+
+		/// <pre>
+		/// Method: freeze_account(origin: OriginFor<T>, asset_id: T::AssetId, who: T::AccountId)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		///            who: T::AccountId - Account to freeze
+		/// 
+		/// Access: PauseOrigin
+		///
+		/// Forbids account to send, receive, mint and burn tokens
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::freeze_account())]
+		pub fn freeze_account(origin: OriginFor<T>, asset_id: T::AssetId, who: T::AccountId) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			Self::ensure_asset_exists(asset_id)?;
+			Frozen::<T, I>::insert(asset_id, &who, true);
+			Self::deposit_event(Event::Frozen { asset_id, who });
+			Ok(().into())
+		}
+
+		/// <pre>
+		/// Method: thaw_account(origin: OriginFor<T>, asset_id: T::AssetId, who: T::AccountId)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		///            who: T::AccountId - Account to thaw
+		/// 
+		/// Access: PauseOrigin
+		///
+		/// Allows frozen account to move tokens again
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::thaw_account())]
+		pub fn thaw_account(origin: OriginFor<T>, asset_id: T::AssetId, who: T::AccountId) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			Self::ensure_asset_exists(asset_id)?;
+			Frozen::<T, I>::remove(asset_id, &who);
+			Self::deposit_event(Event::Thawed { asset_id, who });
+			Ok(().into())
+		}
+
+		/// <pre>
+		/// Method: set_cap(origin: OriginFor<T>, asset_id: T::AssetId, cap: Option<T::Balance>)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		///            cap: Option<T::Balance> - Maximum total supply, `None` removes the cap
+		/// 
+		/// Access: CapOrigin
+		///
+		/// Changes the supply cap of a token, the cap can not be below the current total supply
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::set_cap())]
+		pub fn set_cap(origin: OriginFor<T>, asset_id: T::AssetId, cap: Option<T::Balance>) -> DispatchResultWithPostInfo {
+			T::CapOrigin::ensure_origin(origin)?;
+			Self::ensure_asset_exists(asset_id)?;
+			match cap {
+				Some(cap) => {
+					ensure!(TotalSupply::<T, I>::get(asset_id) <= cap, Error::<T, I>::CapExceeded);
+					Cap::<T, I>::insert(asset_id, cap);
+				}
+				None => Cap::<T, I>::remove(asset_id),
+			}
+			Self::deposit_event(Event::CapSet { asset_id, cap });
+			Ok(().into())
+		}
+
+		/// <pre>
+		/// Method: snapshot(origin: OriginFor<T>, asset_id: T::AssetId)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		/// 
+		/// Access: SnapshotOrigin
+		///
+		/// Records current balances and total supply of a token under a new snapshot id
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::snapshot())]
+		pub fn snapshot(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResultWithPostInfo {
+			T::SnapshotOrigin::ensure_origin(origin)?;
+			Self::ensure_asset_exists(asset_id)?;
+			let snapshot_id = CurrentSnapshotId::<T, I>::try_mutate(asset_id, |id| -> Result<SnapshotId, DispatchError> {
+				*id = id.checked_add(1).ok_or(Error::<T, I>::NoAvailableSnapshotId)?;
+				Ok(*id)
+			})?;
+			Self::deposit_event(Event::Snapshot { asset_id, snapshot_id });
+			Ok(().into())
+		}
+
+		/// <pre>
+		/// Method: delegate(origin: OriginFor<T>, asset_id: T::AssetId, delegatee: T::AccountId)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		///            delegatee: T::AccountId - Account to receive the caller's voting power
+		/// 
+		/// Access: Any account
+		///
+		/// Delegates voting power of the caller's tokens, holders delegate to themselves
+		/// to vote with their own balance
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::delegate())]
+		pub fn delegate(origin: OriginFor<T>, asset_id: T::AssetId, delegatee: T::AccountId) -> DispatchResultWithPostInfo {
+			let delegator = ensure_signed(origin)?;
+			Self::_delegate(asset_id, delegator, delegatee)
+		}
+
+		/// <pre>
+		/// Method: delegate_by_sig(origin: OriginFor<T>,
+		///							asset_id: T::AssetId,
+		///							delegator: T::AccountId,
+		///							delegatee: T::AccountId,
+		///							expiry: T::BlockNumber,
+		///							signature: T::Signature)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		///            delegator: T::AccountId - Account that signed the delegation
+		///            delegatee: T::AccountId - Account to receive the voting power
+		///            expiry: T::BlockNumber - last block the delegation is valid at
+		///            signature: T::Signature - delegator signature over delegation_payload
+		/// 
+		/// Access: Any account
+		///
+		/// Delegates voting power of the delegator's tokens, given the delegator signed it
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::delegate_by_sig())]
+		pub fn delegate_by_sig(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			delegator: T::AccountId,
+			delegatee: T::AccountId,
+			expiry: T::BlockNumber,
+			signature: T::Signature
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			ensure!(frame_system::Module::<T>::block_number() <= expiry, Error::<T, I>::DelegationExpired);
+			Self::ensure_asset_exists(asset_id)?;
+
+			let nonce = PermitNonces::<T, I>::get(&delegator);
+			let payload = Self::delegation_payload(asset_id, &delegator, &delegatee, nonce, expiry);
+			ensure!(signature.verify(&payload[..], &delegator), Error::<T, I>::BadDelegationSignature);
+
+			PermitNonces::<T, I>::insert(&delegator, nonce + One::one());
+			Self::_delegate(asset_id, delegator, delegatee)
+		}
+
+		/// <pre>
+		/// Method: vest(origin: OriginFor<T>, asset_id: T::AssetId)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		/// 
+		/// Access: Vesting account
+		///
+		/// Reports the amount of caller tokens still locked,
+		/// the schedule is removed once everything is released
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::vest())]
+		pub fn vest(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::_vest(asset_id, who)
+		}
+
+		/// <pre>
+		/// Method: vested_transfer(origin: OriginFor<T>,
+		///							asset_id: T::AssetId,
+		///							target: T::AccountId,
+		///							schedule: VestingInfo<T::Balance, T::BlockNumber>)
+		/// 
+		/// Arguments: origin: OriginFor<T> - Transaction caller
+		///            asset_id: T::AssetId - Token id
+		///            target: T::AccountId - Account to receive vesting tokens
+		///            schedule: VestingInfo<T::Balance, T::BlockNumber> - schedule of schedule.locked tokens
+		/// 
+		/// Access: Any account
+		///
+		/// Moves schedule.locked tokens from caller to target and locks them by the schedule,
+		/// schedule.locked must be at least MinVestedTransfer
+		/// </pre>
+		#[pallet::weight(T::WeightInfo::vested_transfer())]
+		#[transactional]
+		pub fn vested_transfer(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			target: T::AccountId,
+			schedule: VestingInfo<T::Balance, T::BlockNumber>
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			ensure!(schedule.is_valid(), Error::<T, I>::InvalidVestingSchedule);
+			ensure!(schedule.locked >= T::MinVestedTransfer::get(), Error::<T, I>::AmountLow);
+			ensure!(
+				!VestingSchedules::<T, I>::contains_key(asset_id, &target),
+				Error::<T, I>::ExistingVestingSchedule
+			);
+
+			Self::_transfer(asset_id, sender, target.clone(), schedule.locked)?;
+			VestingSchedules::<T, I>::insert(asset_id, &target, schedule);
+			Self::deposit_event(Event::VestingCreated { asset_id, who: target, locked: schedule.locked });
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Checks that token with `asset_id` is registered
+		pub fn ensure_asset_exists(asset_id: T::AssetId) -> DispatchResult {
+			ensure!(asset_id < NextAssetId::<T, I>::get(), Error::<T, I>::UnknownAsset);
+			Ok(())
+		}
+
+		/// Message the owner signs to permit `spender` to spend `value` of `asset_id` tokens.
+		/// It is bound to the chain by genesis hash and to the pallet instance by its name
+		pub fn permit_payload(
+			asset_id: T::AssetId,
+			owner: &T::AccountId,
+			spender: &T::AccountId,
+			value: T::Balance,
+			nonce: T::Index,
+			deadline: T::BlockNumber
+		) -> Vec<u8> {
+			let genesis_hash = frame_system::Module::<T>::block_hash(T::BlockNumber::zero());
+			let pallet_name = <T as frame_system::Config>::PalletInfo::name::<Self>().unwrap_or_default();
+			(
+				PERMIT_DOMAIN,
+				genesis_hash,
+				pallet_name.as_bytes(),
+				asset_id,
+				owner,
+				spender,
+				value,
+				nonce,
+				deadline,
+			).encode()
+		}
+
+		/// Message the delegator signs to delegate its voting power to `delegatee`.
+		/// It is bound to the chain and the pallet instance the same way as `permit_payload`
+		pub fn delegation_payload(
+			asset_id: T::AssetId,
+			delegator: &T::AccountId,
+			delegatee: &T::AccountId,
+			nonce: T::Index,
+			expiry: T::BlockNumber
+		) -> Vec<u8> {
+			let genesis_hash = frame_system::Module::<T>::block_hash(T::BlockNumber::zero());
+			let pallet_name = <T as frame_system::Config>::PalletInfo::name::<Self>().unwrap_or_default();
+			(
+				DELEGATION_DOMAIN,
+				genesis_hash,
+				pallet_name.as_bytes(),
+				asset_id,
+				delegator,
+				delegatee,
+				nonce,
+				expiry,
+			).encode()
+		}
+
+		/// Sets `delegatee` as the delegate of `delegator` and moves its voting power,
+		/// which counts both free and reserved tokens
+		pub fn _delegate(asset_id: T::AssetId, delegator: T::AccountId, delegatee: T::AccountId) -> DispatchResultWithPostInfo {
+			Self::ensure_asset_exists(asset_id)?;
+			let previous = Delegates::<T, I>::get(asset_id, &delegator);
+			Delegates::<T, I>::insert(asset_id, &delegator, &delegatee);
+			Self::move_voting_power(
+				asset_id,
+				previous.as_ref(),
+				Some(&delegatee),
+				BalanceOf::<T, I>::get(asset_id, &delegator)
+					.saturating_add(ReservedBalance::<T, I>::get(asset_id, &delegator))
+			);
+			Self::deposit_event(Event::DelegateChanged {
+				asset_id,
+				delegator,
+				from_delegate: previous,
+				to_delegate: delegatee,
+			});
+			Ok(().into())
+		}
+
+		/// Moves `amount` of voting power between delegates, `None` stands for tokens
+		/// without a delegate, i.e. minted, burned or held by a non delegating account
+		pub(crate) fn move_voting_power(
+			asset_id: T::AssetId,
+			src: Option<&T::AccountId>,
+			dst: Option<&T::AccountId>,
+			amount: T::Balance
+		) {
+			if src == dst || amount.is_zero() {
+				return;
+			}
+			if let Some(src) = src {
+				Self::write_checkpoint(asset_id, src, |votes| votes.saturating_sub(amount));
+			}
+			if let Some(dst) = dst {
+				Self::write_checkpoint(asset_id, dst, |votes| votes.saturating_add(amount));
+			}
+		}
+
+		/// Moves voting power along with `amount` of tokens sent from `from` to `to`
+		pub(crate) fn move_delegated_votes(
+			asset_id: T::AssetId,
+			from: Option<&T::AccountId>,
+			to: Option<&T::AccountId>,
+			amount: T::Balance
+		) {
+			let src = from.and_then(|from| Delegates::<T, I>::get(asset_id, from));
+			let dst = to.and_then(|to| Delegates::<T, I>::get(asset_id, to));
+			Self::move_voting_power(asset_id, src.as_ref(), dst.as_ref(), amount);
+		}
+
+		/// Records the voting power of `delegatee` at the current block
+		fn write_checkpoint(asset_id: T::AssetId, delegatee: &T::AccountId, op: impl FnOnce(T::Balance) -> T::Balance) {
+			let block_number = frame_system::Module::<T>::block_number();
+			let count = NumCheckpoints::<T, I>::get(asset_id, delegatee);
+			let last = count.checked_sub(1)
+				.and_then(|index| Checkpoints::<T, I>::get(asset_id, (delegatee.clone(), index)).map(|last| (index, last)));
+			let previous = last.map(|(_, (_, votes))| votes).unwrap_or_else(Zero::zero);
+			let new = op(previous);
+			match last {
+				Some((index, (block, _))) if block == block_number => {
+					Checkpoints::<T, I>::insert(asset_id, (delegatee.clone(), index), (block_number, new));
+				},
+				_ => {
+					Checkpoints::<T, I>::insert(asset_id, (delegatee.clone(), count), (block_number, new));
+					NumCheckpoints::<T, I>::insert(asset_id, delegatee, count.saturating_add(1));
+				},
+			}
+			Self::deposit_event(Event::DelegateVotesChanged {
+				asset_id,
+				delegate: delegatee.clone(),
+				previous_votes: previous,
+				new_votes: new,
+			});
+		}
+
+		/// Amount of `who` tokens locked by vesting at the current block
+		pub fn vesting_locked(asset_id: T::AssetId, who: &T::AccountId) -> T::Balance {
+			VestingSchedules::<T, I>::get(asset_id, who)
+				.map(|schedule| {
+					schedule.locked_at::<T::BlockNumberToBalance>(frame_system::Module::<T>::block_number())
+				})
+				.unwrap_or_else(Zero::zero)
+		}
+
+		/// Updates the vesting schedule of `who` to the amount still locked, removes it once fully released
+		pub fn _vest(asset_id: T::AssetId, who: T::AccountId) -> DispatchResultWithPostInfo {
+			ensure!(VestingSchedules::<T, I>::contains_key(asset_id, &who), Error::<T, I>::NotVesting);
+			let locked = Self::vesting_locked(asset_id, &who);
+			if locked.is_zero() {
+				VestingSchedules::<T, I>::remove(asset_id, &who);
+				Self::deposit_event(Event::VestingCompleted { asset_id, who });
+			} else {
+				Self::deposit_event(Event::VestingUpdated { asset_id, who, still_locked: locked });
+			}
+			Ok(().into())
+		}
+
+		/// Balance of `who` at the time snapshot `snapshot_id` was taken,
+		/// `None` if the snapshot does not exist
+		pub fn balance_of_at(asset_id: T::AssetId, who: &T::AccountId, snapshot_id: SnapshotId) -> Option<T::Balance> {
+			Self::value_at(
+				asset_id,
+				NumAccountSnapshots::<T, I>::get(asset_id, who),
+				|index| AccountSnapshots::<T, I>::get(asset_id, (who.clone(), index)),
+				snapshot_id
+			).map(|value| value.unwrap_or_else(|| BalanceOf::<T, I>::get(asset_id, who)))
+		}
+
+		/// Total supply at the time snapshot `snapshot_id` was taken, `None` if the snapshot does not exist
+		pub fn total_supply_at(asset_id: T::AssetId, snapshot_id: SnapshotId) -> Option<T::Balance> {
+			Self::value_at(
+				asset_id,
+				NumTotalSupplySnapshots::<T, I>::get(asset_id),
+				|index| TotalSupplySnapshots::<T, I>::get(asset_id, index),
+				snapshot_id
+			).map(|value| value.unwrap_or_else(|| TotalSupply::<T, I>::get(asset_id)))
+		}
+
+		/// Looks up the first of `count` checkpoints recorded after `snapshot_id` was taken.
+		/// `Some(None)` means the value did not change since then and the current one applies
+		fn value_at(
+			asset_id: T::AssetId,
+			count: u32,
+			checkpoint: impl Fn(u32) -> Option<(SnapshotId, T::Balance)>,
+			snapshot_id: SnapshotId
+		) -> Option<Option<T::Balance>> {
+			if snapshot_id.is_zero() || snapshot_id > CurrentSnapshotId::<T, I>::get(asset_id) {
+				return None;
+			}
+			let index = partition_point(count, |index| matches!(checkpoint(index), Some((id, _)) if id < snapshot_id));
+			Some(checkpoint(index).map(|(_, value)| value))
+		}
+
+		/// Records the balance of `who` for the current snapshot before it is changed
+		pub(crate) fn update_account_snapshot(asset_id: T::AssetId, who: &T::AccountId) {
+			let current_id = CurrentSnapshotId::<T, I>::get(asset_id);
+			if current_id.is_zero() {
+				return;
+			}
+			let count = NumAccountSnapshots::<T, I>::get(asset_id, who);
+			if Self::needs_checkpoint(count, |index| AccountSnapshots::<T, I>::get(asset_id, (who.clone(), index)), current_id) {
+				AccountSnapshots::<T, I>::insert(asset_id, (who.clone(), count), (current_id, BalanceOf::<T, I>::get(asset_id, who)));
+				NumAccountSnapshots::<T, I>::insert(asset_id, who, count.saturating_add(1));
+			}
+		}
+
+		/// Records the total supply for the current snapshot before it is changed
+		pub(crate) fn update_total_supply_snapshot(asset_id: T::AssetId) {
+			let current_id = CurrentSnapshotId::<T, I>::get(asset_id);
+			if current_id.is_zero() {
+				return;
+			}
+			let count = NumTotalSupplySnapshots::<T, I>::get(asset_id);
+			if Self::needs_checkpoint(count, |index| TotalSupplySnapshots::<T, I>::get(asset_id, index), current_id) {
+				TotalSupplySnapshots::<T, I>::insert(asset_id, count, (current_id, TotalSupply::<T, I>::get(asset_id)));
+				NumTotalSupplySnapshots::<T, I>::insert(asset_id, count.saturating_add(1));
+			}
+		}
+
+		/// Whether the last of `count` checkpoints was recorded before snapshot `current_id`
+		fn needs_checkpoint(
+			count: u32,
+			checkpoint: impl FnOnce(u32) -> Option<(SnapshotId, T::Balance)>,
+			current_id: SnapshotId
+		) -> bool {
+			!matches!(count.checked_sub(1).and_then(checkpoint), Some((id, _)) if id >= current_id)
+		}
+
+		/// Free balance of `who` that can be transferred, vesting and locks deducted.
+		/// `keep_alive` also keeps MinimumBalance on the account
+		pub fn reducible_balance(asset_id: T::AssetId, who: &T::AccountId, keep_alive: bool) -> T::Balance {
+			let mut locked = Self::vesting_locked(asset_id, who)
+				.max(Self::locked_balance(asset_id, who, WithdrawReasons::TRANSFER));
+			if keep_alive {
+				locked = locked.max(T::MinimumBalance::get());
+			}
+			BalanceOf::<T, I>::get(asset_id, who).saturating_sub(locked)
+		}
+
+		/// Amount of free balance of `who` that locks forbid to withdraw for `reasons`
+		pub fn locked_balance(asset_id: T::AssetId, who: &T::AccountId, reasons: WithdrawReasons) -> T::Balance {
+			Locks::<T, I>::get(asset_id, who)
+				.iter()
+				.filter(|lock| lock.reasons.intersects(reasons))
+				.fold(Zero::zero(), |locked, lock| locked.max(lock.amount))
+		}
+
+		/// Moves `amount` from free to reserved balance of `who`
+		pub fn reserve(asset_id: T::AssetId, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+			Self::ensure_asset_exists(asset_id)?;
+			Self::ensure_not_paused(asset_id)?;
+			Self::ensure_not_frozen(asset_id, who)?;
+			if amount.is_zero() {
+				return Ok(());
+			}
+			let new_free = BalanceOf::<T, I>::get(asset_id, who)
+				.checked_sub(&amount)
+				.ok_or(Error::<T, I>::InsufficientBalance)?;
+			ensure!(new_free >= Self::vesting_locked(asset_id, who), Error::<T, I>::LockedByVesting);
+			ensure!(
+				new_free >= Self::locked_balance(asset_id, who, WithdrawReasons::RESERVE),
+				Error::<T, I>::LiquidityRestrictions
+			);
+			let new_reserved = ReservedBalance::<T, I>::get(asset_id, who)
+				.checked_add(&amount)
+				.ok_or(Error::<T, I>::BalanceOverflow)?;
+
+			Self::update_account_snapshot(asset_id, who);
+			Self::write_free_balance(asset_id, who, new_free);
+			Self::write_reserved_balance(asset_id, who, new_reserved);
+			Self::deposit_event(Event::Reserved { asset_id, who: who.clone(), amount });
+			Ok(())
+		}
+
+		/// Moves up to `amount` from reserved to free balance of `who`,
+		/// returns the part of `amount` that was not reserved
+		pub fn unreserve(asset_id: T::AssetId, who: &T::AccountId, amount: T::Balance) -> T::Balance {
+			let reserved = ReservedBalance::<T, I>::get(asset_id, who);
+			let actual = amount.min(reserved);
+			if actual.is_zero() {
+				return amount;
+			}
+			Self::update_account_snapshot(asset_id, who);
+			Self::write_reserved_balance(asset_id, who, reserved - actual);
+			Self::write_free_balance(asset_id, who, BalanceOf::<T, I>::get(asset_id, who).saturating_add(actual));
+			Self::deposit_event(Event::Unreserved { asset_id, who: who.clone(), amount: actual });
+			amount - actual
+		}
+
+		/// Moves up to `amount` of the reserved balance of `slashed` to the free or reserved
+		/// balance of `beneficiary`, returns the part of `amount` that was not reserved
+		pub fn repatriate_reserved(
+			asset_id: T::AssetId,
+			slashed: &T::AccountId,
+			beneficiary: &T::AccountId,
+			amount: T::Balance,
+			status: BalanceStatus
+		) -> Result<T::Balance, DispatchError> {
+			Self::ensure_asset_exists(asset_id)?;
+			Self::ensure_not_paused(asset_id)?;
+			if slashed == beneficiary {
+				return match status {
+					BalanceStatus::Free => Ok(Self::unreserve(asset_id, slashed, amount)),
+					BalanceStatus::Reserved => Ok(amount.saturating_sub(ReservedBalance::<T, I>::get(asset_id, slashed))),
+				};
+			}
+			Self::ensure_not_frozen(asset_id, beneficiary)?;
+
+			let reserved = ReservedBalance::<T, I>::get(asset_id, slashed);
+			let actual = amount.min(reserved);
+			match status {
+				BalanceStatus::Free => {
+					let new_free = BalanceOf::<T, I>::get(asset_id, beneficiary)
+						.checked_add(&actual)
+						.ok_or(Error::<T, I>::BalanceOverflow)?;
+					Self::update_account_snapshot(asset_id, beneficiary);
+					Self::write_free_balance(asset_id, beneficiary, new_free);
+				}
+				BalanceStatus::Reserved => {
+					let new_reserved = ReservedBalance::<T, I>::get(asset_id, beneficiary)
+						.checked_add(&actual)
+						.ok_or(Error::<T, I>::BalanceOverflow)?;
+					Self::write_reserved_balance(asset_id, beneficiary, new_reserved);
+				}
+			}
+			Self::write_reserved_balance(asset_id, slashed, reserved - actual);
+			Self::move_delegated_votes(asset_id, Some(slashed), Some(beneficiary), actual);
+			Self::deposit_event(Event::ReserveRepatriated {
+				asset_id,
+				from: slashed.clone(),
+				to: beneficiary.clone(),
+				amount: actual,
+				status,
+			});
+			Ok(amount - actual)
+		}
+
+		/// Sets a lock `id` on the free balance of `who`, replacing the lock with the same id
+		pub fn set_lock(
+			asset_id: T::AssetId,
+			id: LockIdentifier,
+			who: &T::AccountId,
+			amount: T::Balance,
+			reasons: WithdrawReasons
+		) {
+			if amount.is_zero() || reasons.is_empty() {
+				return Self::remove_lock(asset_id, id, who);
+			}
+			let lock = BalanceLock { id, amount, reasons };
+			let mut locks = Locks::<T, I>::get(asset_id, who);
+			match locks.iter_mut().find(|lock| lock.id == id) {
+				Some(existing) => *existing = lock,
+				None => locks.push(lock),
+			}
+			Locks::<T, I>::insert(asset_id, who, locks);
+		}
+
+		/// Extends lock `id` to at least `amount` and `reasons`, sets it if it does not exist
+		pub fn extend_lock(
+			asset_id: T::AssetId,
+			id: LockIdentifier,
+			who: &T::AccountId,
+			amount: T::Balance,
+			reasons: WithdrawReasons
+		) {
+			let (amount, reasons) = match Locks::<T, I>::get(asset_id, who).into_iter().find(|lock| lock.id == id) {
+				Some(lock) => (lock.amount.max(amount), lock.reasons | reasons),
+				None => (amount, reasons),
+			};
+			Self::set_lock(asset_id, id, who, amount, reasons)
+		}
+
+		/// Removes lock `id` from `who`
+		pub fn remove_lock(asset_id: T::AssetId, id: LockIdentifier, who: &T::AccountId) {
+			let mut locks = Locks::<T, I>::get(asset_id, who);
+			locks.retain(|lock| lock.id != id);
+			if locks.is_empty() {
+				Locks::<T, I>::remove(asset_id, who);
+			} else {
+				Locks::<T, I>::insert(asset_id, who, locks);
+			}
+		}
+
+		/// Checks that transfers of token `asset_id` are not paused
+		pub fn ensure_not_paused(asset_id: T::AssetId) -> DispatchResult {
+			ensure!(!Paused::<T, I>::get(asset_id), Error::<T, I>::TokenPaused);
+			Ok(())
+		}
+
+		/// Checks that `who` is not frozen for token `asset_id`
+		pub fn ensure_not_frozen(asset_id: T::AssetId, who: &T::AccountId) -> DispatchResult {
+			ensure!(!Frozen::<T, I>::get(asset_id, who), Error::<T, I>::AccountFrozen);
+			Ok(())
+		}
+
+		/// Checks that `who` is not the all zero account, which used to stand for
+		/// the mint source and burn destination and can't be owned by anyone
+		pub fn ensure_not_sentinel(who: &T::AccountId) -> DispatchResult {
+			ensure!(!who.using_encoded(|bytes| bytes.iter().all(|b| *b == 0)), Error::<T, I>::SentinelAccount);
+			Ok(())
+		}
+
+		/// Checks that name and symbol fit into StringLimit
+		pub fn ensure_metadata_bounds(name: &[u8], symbol: &[u8]) -> DispatchResult {
+			let limit = T::StringLimit::get() as usize;
+			ensure!(name.len() <= limit && symbol.len() <= limit, Error::<T, I>::BadMetadata);
+			Ok(())
+		}
+
+		/// Transfers tokens from account to another account, sender balance left below
+		/// MinimumBalance is removed as dust
+		pub fn _transfer(
+			asset_id: T::AssetId,
+			from: T::AccountId,
+			to: T::AccountId,
+			amount: T::Balance
+		) -> DispatchResultWithPostInfo {
+			Self::_transfer_with_existence(asset_id, from, to, amount, ExistenceRequirement::AllowDeath)
+		}
+
+		/// Transfers tokens from account to another account, fails with `KeepAlive` if `existence`
+		/// is `KeepAlive` and the sender would be left below MinimumBalance
+		pub fn _transfer_with_existence(
+			asset_id: T::AssetId,
+			from: T::AccountId,
+			to: T::AccountId,
+			amount: T::Balance,
+			existence: ExistenceRequirement
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_asset_exists(asset_id)?;
+			Self::ensure_not_paused(asset_id)?;
+			Self::ensure_not_frozen(asset_id, &from)?;
+			Self::ensure_not_frozen(asset_id, &to)?;
+			Self::ensure_not_sentinel(&from)?;
+			Self::ensure_not_sentinel(&to)?;
+			ensure!(!T::ForbidSelfTransfer::get() || from != to, Error::<T, I>::SelfTransfer);
+			ensure!(!T::ForbidZeroAmount::get() || !amount.is_zero(), Error::<T, I>::ZeroAmount);
+			Self::update_account_snapshot(asset_id, &from);
+			Self::update_account_snapshot(asset_id, &to);
+			let vesting_locked = Self::vesting_locked(asset_id, &from);
+			let locked = Self::locked_balance(asset_id, &from, WithdrawReasons::TRANSFER);
+
+			let from_balance = BalanceOf::<T, I>::get(asset_id, &from);
+			ensure!(from_balance >= amount, Error::<T, I>::TransferAmountExceedsBalance);
+			let new_from_balance = from_balance - amount;
+			ensure!(new_from_balance >= vesting_locked, Error::<T, I>::LockedByVesting);
+			ensure!(new_from_balance >= locked, Error::<T, I>::LiquidityRestrictions);
+
+			let mut dust = None;
+			let (mut reported_from, mut reported_to) = (from_balance, from_balance);
+			if from != to {
+				if existence == ExistenceRequirement::KeepAlive {
+					let left = new_from_balance.saturating_add(ReservedBalance::<T, I>::get(asset_id, &from));
+					ensure!(left >= T::MinimumBalance::get(), Error::<T, I>::KeepAlive);
+				}
+				// locked funds are never swept, the account stays below the minimum instead
+				if Self::is_dust(asset_id, &from, new_from_balance) && vesting_locked.is_zero() && locked.is_zero() {
+					dust = Some(new_from_balance);
+				}
+				let new_to_balance = BalanceOf::<T, I>::get(asset_id, &to)
+					.checked_add(&amount)
+					.ok_or(Error::<T, I>::BalanceOverflow)?;
+				ensure!(!Self::is_dust(asset_id, &to, new_to_balance), Error::<T, I>::ExistentialDeposit);
+
+				Self::write_free_balance(asset_id, &from, new_from_balance);
+				Self::write_free_balance(asset_id, &to, new_to_balance);
+				reported_from = new_from_balance;
+				reported_to = new_to_balance;
+				Self::move_delegated_votes(asset_id, Some(&from), Some(&to), amount);
+			}
+			Self::deposit_event(Event::Transfer {
+				asset_id,
+				from: from.clone(),
+				to,
+				amount,
+				from_balance: reported_from,
+				to_balance: reported_to,
+			});
+			if let Some(dust) = dust {
+				Self::remove_dust(asset_id, &from, dust);
+			}
+			Ok(().into())
+		}
+
+		/// Whether `free` balance of `who` is a non zero amount below MinimumBalance
+		pub fn is_dust(asset_id: T::AssetId, who: &T::AccountId, free: T::Balance) -> bool {
+			!free.is_zero() && free.saturating_add(ReservedBalance::<T, I>::get(asset_id, who)) < T::MinimumBalance::get()
+		}
+
+		/// Burns `dust` left as the free balance of `who` and passes it to DustRemoval
+		fn remove_dust(asset_id: T::AssetId, who: &T::AccountId, dust: T::Balance) {
+			Self::write_free_balance(asset_id, who, Zero::zero());
+			Self::update_total_supply_snapshot(asset_id);
+			TotalSupply::<T, I>::mutate(asset_id, |total| *total = total.saturating_sub(dust));
+			Self::move_delegated_votes(asset_id, Some(who), None, dust);
+			Self::deposit_event(Event::DustLost { asset_id, who: who.clone(), amount: dust });
+			T::DustRemoval::on_dust(asset_id, who, dust);
+		}
+
+		/// Writes the free balance of `who`, the storage entry is removed at zero
+		pub(crate) fn write_free_balance(asset_id: T::AssetId, who: &T::AccountId, amount: T::Balance) {
+			if amount.is_zero() {
+				BalanceOf::<T, I>::remove(asset_id, who);
+			} else {
+				BalanceOf::<T, I>::insert(asset_id, who, amount);
+			}
+		}
+
+		/// Writes the reserved balance of `who`, the storage entry is removed at zero
+		pub(crate) fn write_reserved_balance(asset_id: T::AssetId, who: &T::AccountId, amount: T::Balance) {
+			if amount.is_zero() {
+				ReservedBalance::<T, I>::remove(asset_id, who);
+			} else {
+				ReservedBalance::<T, I>::insert(asset_id, who, amount);
+			}
+		}
+
+		/// Sets `amount` as the allowance of `spender` over the `owner` s tokens.
+		/// Keeps the expiry of the allowance unless it is reset to zero
+		pub fn _approve(
+			asset_id: T::AssetId,
+			owner: T::AccountId,
+			spender: T::AccountId,
+			amount: T::Balance
+		) -> DispatchResultWithPostInfo {
+			AllowanceOf::<T, I>::try_mutate((asset_id, &owner), &spender, |bal| -> DispatchResultWithPostInfo {
+				*bal = amount;
+				Ok(().into())
+			})?;
+			if amount.is_zero() {
+				AllowanceExpiry::<T, I>::remove((asset_id, &owner), &spender);
+			}
+			Self::deposit_event(Event::Approval { asset_id, owner, spender, amount });
+			Ok(().into())
+		}
+
+		/// Sets `amount` as the allowance of `spender` over the `owner` s tokens,
+		/// replacing its expiry with `expires_at`. `None` makes the allowance never expire
+		pub fn _approve_with_expiry(
+			asset_id: T::AssetId,
+			owner: T::AccountId,
+			spender: T::AccountId,
+			amount: T::Balance,
+			expires_at: Option<T::BlockNumber>
+		) -> DispatchResultWithPostInfo {
+			match expires_at {
+				Some(expires_at) => {
+					AllowanceOf::<T, I>::insert((asset_id, &owner), &spender, amount);
+					AllowanceExpiry::<T, I>::insert((asset_id, &owner), &spender, expires_at);
+					Self::deposit_event(Event::ApprovalWithExpiry { asset_id, owner, spender, amount, expires_at });
+				},
+				None => {
+					AllowanceExpiry::<T, I>::remove((asset_id, &owner), &spender);
+					Self::_approve(asset_id, owner, spender, amount)?;
+				}
+			}
+			Ok(().into())
+		}
+
+		/// Removes the allowance of `spender` over the `owner` s tokens together with its expiry
+		pub fn _revoke_allowance(asset_id: T::AssetId, owner: T::AccountId, spender: T::AccountId) {
+			AllowanceOf::<T, I>::remove((asset_id, &owner), &spender);
+			AllowanceExpiry::<T, I>::remove((asset_id, &owner), &spender);
+			Self::deposit_event(Event::Approval { asset_id, owner, spender, amount: Zero::zero() });
+		}
+
+		/// Spenders of the `owner` s tokens with their allowances
+		pub fn allowances_of(asset_id: T::AssetId, owner: &T::AccountId) -> Vec<(T::AccountId, T::Balance)> {
+			AllowanceOf::<T, I>::iter_prefix((asset_id, owner)).collect()
+		}
+
+		/// Updates `owner` s allowance for `spender` based on spent `amount`
+		pub fn _spend_allowance(
+			asset_id: T::AssetId,
+			owner: T::AccountId,
+			spender: T::AccountId,
+			amount: T::Balance
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_not_paused(asset_id)?;
+			Self::ensure_not_frozen(asset_id, &owner)?;
+			Self::ensure_not_frozen(asset_id, &spender)?;
+			if let Some(expires_at) = AllowanceExpiry::<T, I>::get((asset_id, &owner), &spender) {
+				ensure!(frame_system::Module::<T>::block_number() <= expires_at, Error::<T, I>::AllowanceExpired);
+			}
+			let current_allowance = AllowanceOf::<T, I>::get((asset_id, &owner), &spender);
+			if current_allowance != T::Balance::max_value() {
+				let new_allowance = current_allowance.checked_sub(&amount)
+									.ok_or( Error::<T, I>::InsufficientAllowance)?;
+				Self::_approve(asset_id, owner, spender, new_allowance)?;
+			}
+			Ok(().into())
+		}
+
+		/// Creates `amount` tokens and assigns them to `account`, increasing the total supply
+		pub fn _mint(asset_id: T::AssetId, account: T::AccountId, amount: T::Balance) -> DispatchResultWithPostInfo {
+			Self::ensure_can_mint(asset_id, &account, amount)?;
+			Self::_mint_unchecked(asset_id, account, amount)
+		}
+
+		/// Checks that `amount` new tokens can be credited to `account`: the token is not paused,
+		/// the account is neither frozen nor the sentinel, the cap holds and no dust is left
+		pub fn ensure_can_mint(asset_id: T::AssetId, account: &T::AccountId, amount: T::Balance) -> DispatchResult {
+			Self::ensure_not_paused(asset_id)?;
+			Self::ensure_not_frozen(asset_id, account)?;
+			Self::ensure_not_sentinel(account)?;
+			if let Some(cap) = Cap::<T, I>::get(asset_id) {
+				let new_supply = TotalSupply::<T, I>::get(asset_id)
+					.checked_add(&amount)
+					.ok_or(Error::<T, I>::BalanceOverflow)?;
+				ensure!(new_supply <= cap, Error::<T, I>::CapExceeded);
+			}
+			let new_balance = BalanceOf::<T, I>::get(asset_id, account).saturating_add(amount);
+			ensure!(!Self::is_dust(asset_id, account, new_balance), Error::<T, I>::ExistentialDeposit);
+			Ok(())
+		}
+
+		/// Same as `_mint` but ignores pause, freeze, cap and minimum balance, used to refund
+		/// transaction fees which only restores supply burned when the fee was withdrawn
+		pub fn _mint_unchecked(asset_id: T::AssetId, account: T::AccountId, amount: T::Balance) -> DispatchResultWithPostInfo {
+			Self::ensure_asset_exists(asset_id)?;
+			let new_supply = TotalSupply::<T, I>::get(asset_id)
+				.checked_add(&amount)
+				.ok_or(Error::<T, I>::BalanceOverflow)?;
+			let new_balance = BalanceOf::<T, I>::get(asset_id, &account)
+				.checked_add(&amount)
+				.ok_or(Error::<T, I>::BalanceOverflow)?;
+			Self::update_account_snapshot(asset_id, &account);
+			Self::update_total_supply_snapshot(asset_id);
+			TotalSupply::<T, I>::insert(asset_id, new_supply);
+			Self::write_free_balance(asset_id, &account, new_balance);
+			Self::move_delegated_votes(asset_id, None, Some(&account), amount);
+			Self::deposit_event(Event::Minted { asset_id, to: account, amount });
+			Ok(().into())
+		}
+
+		/// Destroys `amount` tokens from `account`, reducing the total supply.
+		/// Tokens under vesting or transfer locks can't be burned
+		pub fn _burn(asset_id: T::AssetId, account: T::AccountId, amount: T::Balance) -> DispatchResultWithPostInfo {
+			Self::ensure_not_paused(asset_id)?;
+			Self::ensure_not_frozen(asset_id, &account)?;
+			Self::ensure_can_burn(asset_id, &account, amount, WithdrawReasons::TRANSFER)?;
+			Self::_burn_unchecked(asset_id, account, amount)
+		}
+
+		/// Checks that `amount` of the free balance of `who` is not held by the locks for
+		/// `reasons` nor, when transfers are among them, by a vesting schedule
+		pub fn ensure_can_burn(
+			asset_id: T::AssetId,
+			who: &T::AccountId,
+			amount: T::Balance,
+			reasons: WithdrawReasons
+		) -> DispatchResult {
+			let new_balance = BalanceOf::<T, I>::get(asset_id, who)
+				.checked_sub(&amount)
+				.ok_or(Error::<T, I>::BurnAmountExceedsBalance)?;
+			if reasons.intersects(WithdrawReasons::TRANSFER) {
+				ensure!(new_balance >= Self::vesting_locked(asset_id, who), Error::<T, I>::LockedByVesting);
+			}
+			ensure!(
+				new_balance >= Self::locked_balance(asset_id, who, reasons),
+				Error::<T, I>::LiquidityRestrictions
+			);
+			Ok(())
+		}
+
+		/// Same as `_burn` but ignores pause, freeze, vesting and locks, used to charge transaction
+		/// fees so that a paused token does not block the extrinsic that unpauses it
+		pub fn _burn_unchecked(asset_id: T::AssetId, account: T::AccountId, amount: T::Balance) -> DispatchResultWithPostInfo {
+			Self::ensure_asset_exists(asset_id)?;
+			let new_balance = BalanceOf::<T, I>::get(asset_id, &account)
+				.checked_sub(&amount)
+				.ok_or(Error::<T, I>::BurnAmountExceedsBalance)?;
+			let new_supply = TotalSupply::<T, I>::get(asset_id)
+				.checked_sub(&amount)
+				.ok_or(Error::<T, I>::BalanceUnderflow)?;
+			Self::update_account_snapshot(asset_id, &account);
+			Self::update_total_supply_snapshot(asset_id);
+			TotalSupply::<T, I>::insert(asset_id, new_supply);
+			Self::write_free_balance(asset_id, &account, new_balance);
+			Self::move_delegated_votes(asset_id, Some(&account), None, amount);
+			Self::deposit_event(Event::Burned { asset_id, from: account, amount });
 			Ok(().into())
 		}
 	}
-}
\ No newline at end of file
+}
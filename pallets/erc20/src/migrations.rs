@@ -0,0 +1,147 @@
+//! Storage migrations, run on runtime upgrade while the stored pallet version is behind the crate version
+
+use frame_support::{
+	codec::Decode,
+	ensure,
+	storage::migration::{have_storage_value, take_storage_value, StorageIterator, StorageKeyIterator},
+	traits::{Get, GetPalletVersion, OnRuntimeUpgrade, PalletInfo, PalletVersion},
+	weights::Weight,
+	sp_runtime::{sp_std::{marker::PhantomData, vec::Vec}, traits::{One, Zero}},
+	Blake2_128Concat,
+};
+use crate::{AllowanceOf, BalanceOf, Config, Decimals, Name, NextAssetId, Pallet, Symbol, TotalSupply};
+
+/// Version 0.2.0: the single token becomes asset zero of the token registry and its
+/// genesis decimals are stored explicitly
+pub mod v2 {
+	use super::*;
+
+	/// Moves the single token of version 0.1.0, whose total supply, name, symbol and
+	/// decimals were plain values and whose balances and allowances were keyed by account
+	/// only, to asset id zero of the registry.
+	///
+	/// Chains built from the registry layout before the genesis `decimals` were honoured
+	/// have no decimals entry for the genesis token, which made it read as `DEFAULT_DECIMALS`.
+	/// `D` is the value from the chain spec of the instance being migrated, it is written
+	/// for both layouts unless decimals are already stored. Must run before `AllModules`,
+	/// e.g. as the custom upgrade of `frame_executive::Executive`, since the pallet stores
+	/// its new version in its own upgrade hook.
+	pub struct MigrateToV2<T, I, D>(PhantomData<(T, I, D)>);
+
+	impl<T: Config<I>, I: 'static, D: Get<u8>> MigrateToV2<T, I, D> {
+		fn needs_migration() -> bool {
+			Pallet::<T, I>::storage_version().is_none_or(|version| version < PalletVersion::new(0, 2, 0))
+		}
+
+		/// Storage prefix of the instance, its name in `construct_runtime`
+		fn module_prefix() -> &'static [u8] {
+			<T as frame_system::Config>::PalletInfo::name::<Pallet<T, I>>()
+				.expect("pallet instance is part of the runtime")
+				.as_bytes()
+		}
+
+		/// Whether the storage is in the single token layout. Its total supply is stored at
+		/// the bare `TotalSupply` prefix, which the registry layout keys by asset id
+		fn has_single_token_layout() -> bool {
+			have_storage_value(Self::module_prefix(), b"TotalSupply", &[])
+		}
+
+		/// Owner and spender of a single token allowance, from the key suffix made of
+		/// two `Blake2_128Concat` hashed accounts
+		fn decode_allowance_key(key: &[u8]) -> Option<(T::AccountId, T::AccountId)> {
+			let mut rest = key.get(16..)?;
+			let owner = T::AccountId::decode(&mut rest).ok()?;
+			let mut rest = rest.get(16..)?;
+			let spender = T::AccountId::decode(&mut rest).ok()?;
+			Some((owner, spender))
+		}
+
+		/// Moves the single token to asset id zero and registers it
+		fn migrate_single_token() -> Weight {
+			let module = Self::module_prefix();
+			let asset_id = T::AssetId::zero();
+
+			let total_supply = take_storage_value::<T::Balance>(module, b"TotalSupply", &[]).unwrap_or_default();
+			let name = take_storage_value::<Vec<u8>>(module, b"Name", &[]).unwrap_or_default();
+			let symbol = take_storage_value::<Vec<u8>>(module, b"Symbol", &[]).unwrap_or_default();
+			let decimals = take_storage_value::<u8>(module, b"Decimals", &[]).unwrap_or_else(D::get);
+			TotalSupply::<T, I>::insert(asset_id, total_supply);
+			Name::<T, I>::insert(asset_id, name);
+			Symbol::<T, I>::insert(asset_id, symbol);
+			Decimals::<T, I>::insert(asset_id, decimals);
+			NextAssetId::<T, I>::put(T::AssetId::one());
+			let mut reads = 4u64;
+			let mut writes = 9u64;
+
+			// old entries share the prefix of the new ones, so they are drained before writing
+			let balances: Vec<(T::AccountId, T::Balance)> =
+				StorageKeyIterator::<T::AccountId, T::Balance, Blake2_128Concat>::new(module, b"BalanceOf")
+					.drain()
+					.collect();
+			for (who, balance) in balances {
+				reads += 1;
+				writes += 1;
+				if !balance.is_zero() {
+					BalanceOf::<T, I>::insert(asset_id, &who, balance);
+					writes += 1;
+				}
+			}
+
+			let allowances: Vec<(Vec<u8>, T::Balance)> =
+				StorageIterator::<T::Balance>::new(module, b"AllowanceOf").drain().collect();
+			for (key, allowance) in allowances {
+				reads += 1;
+				writes += 1;
+				if let Some((owner, spender)) = Self::decode_allowance_key(&key) {
+					if !allowance.is_zero() {
+						AllowanceOf::<T, I>::insert((asset_id, owner), spender, allowance);
+						writes += 1;
+					}
+				}
+			}
+
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		/// Checks to run before the migration, e.g. from a try-runtime harness
+		pub fn pre_upgrade() -> Result<(), &'static str> {
+			if Self::needs_migration() && Self::has_single_token_layout() {
+				ensure!(NextAssetId::<T, I>::get().is_zero(), "single token and registry layouts are both present");
+			}
+			Ok(())
+		}
+
+		/// Checks to run after the migration, e.g. from a try-runtime harness
+		pub fn post_upgrade() -> Result<(), &'static str> {
+			ensure!(!Self::has_single_token_layout(), "single token layout is left in storage");
+			ensure!(
+				NextAssetId::<T, I>::get().is_zero() || Decimals::<T, I>::contains_key(T::AssetId::zero()),
+				"genesis token decimals were not stored"
+			);
+			Ok(())
+		}
+	}
+
+	impl<T: Config<I>, I: 'static, D: Get<u8>> OnRuntimeUpgrade for MigrateToV2<T, I, D> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight = T::DbWeight::get().reads(1);
+			if !Self::needs_migration() {
+				return weight;
+			}
+
+			weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			if Self::has_single_token_layout() {
+				return weight.saturating_add(Self::migrate_single_token());
+			}
+
+			// registry layout, an instance added by this upgrade has no genesis token
+			let asset_id = T::AssetId::zero();
+			weight = weight.saturating_add(T::DbWeight::get().reads(2));
+			if !NextAssetId::<T, I>::get().is_zero() && !Decimals::<T, I>::contains_key(asset_id) {
+				Decimals::<T, I>::insert(asset_id, D::get());
+				weight = weight.saturating_add(T::DbWeight::get().writes(1));
+			}
+			weight
+		}
+	}
+}
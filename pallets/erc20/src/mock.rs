@@ -1,9 +1,18 @@
 #![allow(clippy::from_over_into)]
 
 use sp_core::H256;
-use frame_support::parameter_types;
+use frame_support::{
+    parameter_types, Blake2_128Concat, StorageHasher,
+    codec::Encode,
+    instances::Instance1,
+    storage::migration::put_storage_value,
+    traits::{Get, PalletInfo as _, PalletVersion},
+    weights::IdentityFee,
+};
+use std::cell::RefCell;
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup}, testing::Header,
+	traits::{BlakeTwo256, ConvertInto, IdentityLookup}, FixedPointNumber, FixedU128,
+	testing::{Header, TestSignature, UintAuthorityId},
 };
 use crate as pallet_erc20;
 
@@ -18,16 +27,80 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Module, Call, Config, Storage, Event<T>},
-        PalletErc20: pallet_erc20::{Module, Call, Storage, Event<T>}
+        TransactionPayment: pallet_transaction_payment::{Module, Storage},
+        PalletErc20: pallet_erc20::{Module, Call, Storage, Event<T>},
+        SecondErc20: pallet_erc20::<Instance1>::{Module, Call, Storage, Event<T>}
 	}
 );
 
-type Balance = u64;
-type AccountId = u64;
+pub type Balance = u64;
+pub type AccountId = u64;
+pub type AssetId = u32;
 
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const SS58Prefix: u8 = 42;
+	pub const GenesisAssetId: AssetId = ASSET_ID;
+	pub const TransactionByteFee: Balance = 1;
+	pub const StringLimit: u32 = 20;
+	pub const MaxLocks: u32 = 50;
+	pub const SecondMinimumBalance: Balance = 1;
+	pub const MaxBatchSize: u32 = 3;
+	pub const MaxSpenders: u32 = 2;
+	pub const MinVestedTransfer: Balance = 100;
+	pub const StrictApprove: bool = false;
+	pub const SecondStrictApprove: bool = true;
+	pub const ForbidSelfTransfer: bool = false;
+	pub const ForbidZeroAmount: bool = false;
+	pub const SecondForbidSelfTransfer: bool = true;
+	pub const SecondForbidZeroAmount: bool = true;
+	pub const MigratedDecimals: u8 = 9;
+	pub FeeConversionRate: FixedU128 = FixedU128::saturating_from_rational(1, 2);
+}
+
+/// Account receiving the dust of the default instance
+pub const DUST_TRAP_ACC: AccountId = 99;
+
+/// Account the benchmarks of signed calls sign with
+#[cfg(feature = "runtime-benchmarks")]
+pub const BENCHMARK_SIGNER_ACC: AccountId = 98;
+
+thread_local! {
+    static MINIMUM_BALANCE: RefCell<Balance> = const { RefCell::new(1) };
+}
+
+/// Minimum balance of the default instance, changeable by tests
+pub struct MinimumBalance;
+impl Get<Balance> for MinimumBalance {
+    fn get() -> Balance {
+        MINIMUM_BALANCE.with(|v| *v.borrow())
+    }
+}
+
+pub fn set_minimum_balance(minimum_balance: Balance) {
+    MINIMUM_BALANCE.with(|v| *v.borrow_mut() = minimum_balance);
+}
+
+/// Mints the dust of the default instance to DUST_TRAP_ACC
+pub struct DustTrap;
+impl pallet_erc20::OnDust<AssetId, AccountId, Balance> for DustTrap {
+    fn on_dust(asset_id: AssetId, _who: &AccountId, amount: Balance) {
+        let _ = PalletErc20::_mint_unchecked(asset_id, DUST_TRAP_ACC, amount);
+    }
+}
+
+/// Signs benchmark payloads with a TestSignature of BENCHMARK_SIGNER_ACC
+#[cfg(feature = "runtime-benchmarks")]
+pub struct TestBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_erc20::BenchmarkHelper<AccountId, TestSignature> for TestBenchmarkHelper {
+    fn signer() -> AccountId {
+        BENCHMARK_SIGNER_ACC
+    }
+
+    fn sign(signer: &AccountId, payload: &[u8]) -> TestSignature {
+        TestSignature(*signer, payload.to_vec())
+    }
 }
 
 impl frame_system::Config for Test {
@@ -58,27 +131,125 @@ impl frame_system::Config for Test {
 impl pallet_erc20::Config for Test {
     type Event = Event;
     type Balance = Balance;
+    type AssetId = AssetId;
+    type CreateOrigin = frame_system::EnsureRoot<AccountId>;
+    type MintOrigin = frame_system::EnsureRoot<AccountId>;
+    type BurnOrigin = frame_system::EnsureRoot<AccountId>;
+    type MetadataOrigin = frame_system::EnsureRoot<AccountId>;
+    type PauseOrigin = frame_system::EnsureRoot<AccountId>;
+    type CapOrigin = frame_system::EnsureRoot<AccountId>;
+    type SnapshotOrigin = frame_system::EnsureRoot<AccountId>;
+    type StringLimit = StringLimit;
+    type BlockNumberToBalance = ConvertInto;
+    type MinVestedTransfer = MinVestedTransfer;
+    type MinimumBalance = MinimumBalance;
+    type DustRemoval = DustTrap;
+    type MaxLocks = MaxLocks;
+    type MaxBatchSize = MaxBatchSize;
+    type MaxSpenders = MaxSpenders;
+    type StrictApprove = StrictApprove;
+    type ForbidSelfTransfer = ForbidSelfTransfer;
+    type ForbidZeroAmount = ForbidZeroAmount;
+    type Signature = TestSignature;
+    type Signer = UintAuthorityId;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = TestBenchmarkHelper;
+    type WeightInfo = ();
 }
 
+/// Fees are paid in the genesis token, two fee units for one token
+pub type FeeAdapter = pallet_erc20::Erc20FeeAdapter<Test, (), GenesisAssetId, FeeConversionRate>;
+
+impl pallet_transaction_payment::Config for Test {
+    type OnChargeTransaction = FeeAdapter;
+    type TransactionByteFee = TransactionByteFee;
+    type WeightToFee = IdentityFee<Balance>;
+    type FeeMultiplierUpdate = ();
+}
+
+impl pallet_erc20::Config<Instance1> for Test {
+    type Event = Event;
+    type Balance = Balance;
+    type AssetId = AssetId;
+    type CreateOrigin = frame_system::EnsureRoot<AccountId>;
+    type MintOrigin = frame_system::EnsureRoot<AccountId>;
+    type BurnOrigin = frame_system::EnsureRoot<AccountId>;
+    type MetadataOrigin = frame_system::EnsureRoot<AccountId>;
+    type PauseOrigin = frame_system::EnsureRoot<AccountId>;
+    type CapOrigin = frame_system::EnsureRoot<AccountId>;
+    type SnapshotOrigin = frame_system::EnsureRoot<AccountId>;
+    type StringLimit = StringLimit;
+    type BlockNumberToBalance = ConvertInto;
+    type MinVestedTransfer = MinVestedTransfer;
+    type MinimumBalance = SecondMinimumBalance;
+    type DustRemoval = ();
+    type MaxLocks = MaxLocks;
+    type MaxBatchSize = MaxBatchSize;
+    type MaxSpenders = MaxSpenders;
+    type StrictApprove = SecondStrictApprove;
+    type ForbidSelfTransfer = SecondForbidSelfTransfer;
+    type ForbidZeroAmount = SecondForbidZeroAmount;
+    type Signature = TestSignature;
+    type Signer = UintAuthorityId;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = TestBenchmarkHelper;
+    type WeightInfo = ();
+}
+
+/// Asset id of the token built at genesis
+pub const ASSET_ID: AssetId = 0;
+
 pub const BALANCES: [(AccountId, Balance); 4] = [(1, 500_000), (2, 300_000), (3, 1000), (4, 0)];
 pub fn get_test_total_supply() -> Balance {
     BALANCES.iter().map(|(_, y)| y).sum()
 }
 
+/// `Currency` view of the genesis token
+pub type GenesisToken = pallet_erc20::TokenOf<Test, (), GenesisAssetId>;
+
+pub const SECOND_DECIMALS: u8 = 6;
+pub const SECOND_BALANCES: [(AccountId, Balance); 2] = [(1, 7_000), (5, 3_000)];
+pub const SECOND_CAP: Balance = 15_000;
+/// `Currency` view of the genesis token of the second instance
+pub type SecondGenesisToken = pallet_erc20::TokenOf<Test, Instance1, GenesisAssetId>;
+/// (account, starting block, cliff, per block release, locked amount)
+pub const SECOND_VESTING: (AccountId, u64, u64, Balance, Balance) = (5, 10, 5, 100, 2_000);
+pub fn get_second_total_supply() -> Balance {
+    SECOND_BALANCES.iter().map(|(_, y)| y).sum()
+}
+
 pub fn get_test_token_name() -> Vec<u8> { String::from("SoraTestToken").as_bytes().to_vec() } 
 pub fn get_test_token_sym() -> Vec<u8> { String::from("STT").as_bytes().to_vec() } 
+pub fn get_second_token_name() -> Vec<u8> { String::from("SoraSecondToken").as_bytes().to_vec() }
+pub fn get_second_token_sym() -> Vec<u8> { String::from("SST").as_bytes().to_vec() }
 
 /// Build genesis storage
 pub fn new_test_ext() -> frame_support::sp_io::TestExternalities {
+    set_minimum_balance(1);
     let mut t = frame_system::GenesisConfig::default()
         .build_storage::<Test>()
         .unwrap();
 
     pallet_erc20::GenesisConfig::<Test> {
+        phantom: Default::default(),
         balances: BALANCES.iter().map(|(x, y)| (*x, *y)).collect(),
         name: get_test_token_name() ,
         sym: get_test_token_sym(),
-        decimals: 18
+        decimals: 18,
+        cap: None,
+        vesting: vec![],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    pallet_erc20::GenesisConfig::<Test, Instance1> {
+        phantom: Default::default(),
+        balances: SECOND_BALANCES.iter().map(|(x, y)| (*x, *y)).collect(),
+        name: get_second_token_name(),
+        sym: get_second_token_sym(),
+        decimals: SECOND_DECIMALS,
+        cap: Some(SECOND_CAP),
+        vesting: vec![SECOND_VESTING],
     }
     .assimilate_storage(&mut t)
     .unwrap();
@@ -88,6 +259,37 @@ pub fn new_test_ext() -> frame_support::sp_io::TestExternalities {
 	ext
 }
 
+/// Allowance given by the first genesis holder to the second one in the single token layout
+pub const SINGLE_TOKEN_ALLOWANCE: Balance = 300;
+
+/// Storage of a chain built by version 0.1.0, before tokens were keyed by asset id:
+/// total supply, name and symbol are plain values, balances are keyed by account and
+/// allowances by owner and spender. Decimals were never written at genesis
+pub fn new_single_token_ext() -> frame_support::sp_io::TestExternalities {
+    let t = frame_system::GenesisConfig::default()
+        .build_storage::<Test>()
+        .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| {
+        let module = <Test as frame_system::Config>::PalletInfo::name::<PalletErc20>().unwrap().as_bytes();
+        put_storage_value(module, b"TotalSupply", &[], get_test_total_supply());
+        put_storage_value(module, b"Name", &[], get_test_token_name());
+        put_storage_value(module, b"Symbol", &[], get_test_token_sym());
+        for (acc, bal) in BALANCES.iter() {
+            put_storage_value(module, b"BalanceOf", &acc.using_encoded(Blake2_128Concat::hash), bal);
+        }
+        let allowance_key = [
+            BALANCES[0].0.using_encoded(Blake2_128Concat::hash),
+            BALANCES[1].0.using_encoded(Blake2_128Concat::hash),
+        ].concat();
+        put_storage_value(module, b"AllowanceOf", &allowance_key, SINGLE_TOKEN_ALLOWANCE);
+        PalletVersion::new(0, 1, 0).put_into_storage::<PalletInfo, PalletErc20>();
+        System::set_block_number(1);
+    });
+    ext
+}
+
 // get and cut last event
 #[allow(clippy::result_unit_err)] 
 pub fn last_event() -> Result<Event, ()> {
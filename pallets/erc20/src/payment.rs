@@ -0,0 +1,89 @@
+//! Transaction fee payment in ERC20 tokens
+
+use super::*;
+use frame_support::{
+	ensure,
+	traits::{Get, WithdrawReasons},
+	sp_runtime::{
+		FixedPointNumber, FixedPointOperand, FixedU128,
+		traits::{DispatchInfoOf, PostDispatchInfoOf, Saturating, Zero},
+		transaction_validity::{InvalidTransaction, TransactionValidityError},
+	},
+	sp_std::marker::PhantomData,
+};
+use pallet_transaction_payment::OnChargeTransaction;
+
+/// Charges transaction fees in the token with asset id `A` of the pallet instance `I`.
+///
+/// The fee computed by `pallet_transaction_payment` is converted to token units with the
+/// `R` rate, withdrawn fee is burned and the part paid for unused weight is minted back.
+/// Tokens under locks for `WithdrawReasons::TRANSACTION_PAYMENT` can't pay fees.
+/// Like `transfer_keep_alive`, the payer keeps at least MinimumBalance, so that neither
+/// the fee nor its refund leaves dust.
+pub struct Erc20FeeAdapter<T, I, A, R>(PhantomData<(T, I, A, R)>);
+
+impl<T, I, A, R> OnChargeTransaction<T> for Erc20FeeAdapter<T, I, A, R> where
+	T: Config<I> + pallet_transaction_payment::Config,
+	T::Balance: FixedPointOperand,
+	I: 'static,
+	A: Get<T::AssetId>,
+	R: Get<FixedU128>,
+{
+	type Balance = T::Balance;
+	/// Amount of tokens withdrawn from the payer
+	type LiquidityInfo = Option<T::Balance>;
+
+	fn withdraw_fee(
+		who: &T::AccountId,
+		_call: &T::Call,
+		_dispatch_info: &DispatchInfoOf<T::Call>,
+		fee: Self::Balance,
+		_tip: Self::Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		let token_fee = Self::to_token_units(fee);
+		if token_fee.is_zero() {
+			return Ok(None);
+		}
+
+		let asset_id = A::get();
+		Pallet::<T, I>::ensure_can_burn(asset_id, who, token_fee, WithdrawReasons::TRANSACTION_PAYMENT)
+			.map_err(|_| InvalidTransaction::Payment)?;
+		let left = BalanceOf::<T, I>::get(asset_id, who)
+			.saturating_sub(token_fee)
+			.saturating_add(ReservedBalance::<T, I>::get(asset_id, who));
+		ensure!(left >= T::MinimumBalance::get(), InvalidTransaction::Payment);
+		Pallet::<T, I>::_burn_unchecked(asset_id, who.clone(), token_fee)
+			.map_err(|_| InvalidTransaction::Payment)?;
+		Ok(Some(token_fee))
+	}
+
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		_dispatch_info: &DispatchInfoOf<T::Call>,
+		_post_info: &PostDispatchInfoOf<T::Call>,
+		corrected_fee: Self::Balance,
+		_tip: Self::Balance,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		if let Some(paid) = already_withdrawn {
+			let refund = paid.saturating_sub(Self::to_token_units(corrected_fee));
+			if !refund.is_zero() {
+				Pallet::<T, I>::_mint_unchecked(A::get(), who.clone(), refund)
+					.map_err(|_| InvalidTransaction::Payment)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+impl<T, I, A, R> Erc20FeeAdapter<T, I, A, R> where
+	T: Config<I>,
+	T::Balance: FixedPointOperand,
+	I: 'static,
+	R: Get<FixedU128>,
+{
+	/// Converts fee to token units with the configured rate
+	pub fn to_token_units(fee: T::Balance) -> T::Balance {
+		R::get().saturating_mul_int(fee)
+	}
+}
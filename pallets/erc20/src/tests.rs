@@ -1,18 +1,32 @@
-use crate::{Error, mock::*, DEFAULT_DECIMALS};
-use frame_support::{assert_ok, assert_noop};
+use crate::{Error, Votes, VestingInfo, Decimals, NextAssetId, mock::*, migrations::v2::MigrateToV2, DEFAULT_DECIMALS};
+use frame_support::{
+    assert_ok, assert_noop, instances::Instance1,
+    traits::{
+        Currency, ReservableCurrency, LockableCurrency, WithdrawReasons, BalanceStatus, ExistenceRequirement,
+        Imbalance, SignedImbalance,
+        GetPalletVersion, OnRuntimeUpgrade, PalletVersion,
+    },
+    weights::{DispatchInfo, PostDispatchInfo, Pays},
+};
+use pallet_transaction_payment::OnChargeTransaction;
+use sp_runtime::{
+    DispatchError,
+    testing::TestSignature,
+    transaction_validity::{InvalidTransaction, TransactionValidityError},
+};
 
 type RuntimeError = Error<Test>;
 
 #[test]
 fn it_works_for_mock_build_genesis() {
     new_test_ext().execute_with(|| {
-        assert_eq!(get_test_total_supply(), PalletErc20::get_total_supply());
+        assert_eq!(get_test_total_supply(), PalletErc20::get_total_supply(ASSET_ID));
         BALANCES.iter().for_each(|(acc, bal)|{
-                assert_eq!(*bal, PalletErc20::get_balance(acc));
+                assert_eq!(*bal, PalletErc20::get_balance(ASSET_ID, acc));
         });
-        assert_eq!(DEFAULT_DECIMALS, PalletErc20::get_decimals());
-        assert_eq!(get_test_token_name(), PalletErc20::get_name());
-        assert_eq!(get_test_token_sym(), PalletErc20::get_symbol());
+        assert_eq!(DEFAULT_DECIMALS, PalletErc20::get_decimals(ASSET_ID));
+        assert_eq!(get_test_token_name(), PalletErc20::get_name(ASSET_ID));
+        assert_eq!(get_test_token_sym(), PalletErc20::get_symbol(ASSET_ID));
     });
 }
 
@@ -23,15 +37,22 @@ fn it_works_transfer_token() {
         let reciever_acc = BALANCES[3].0;
         let amount = 500;
 
-        let sender_bal_before = PalletErc20::get_balance(sender_acc);
-        let reciever_bal_before = PalletErc20::get_balance(reciever_acc);
-        let transfer_result = PalletErc20::transfer(Origin::signed(sender_acc), reciever_acc, amount);
+        let sender_bal_before = PalletErc20::get_balance(ASSET_ID, sender_acc);
+        let reciever_bal_before = PalletErc20::get_balance(ASSET_ID, reciever_acc);
+        let transfer_result = PalletErc20::transfer(Origin::signed(sender_acc), ASSET_ID, reciever_acc, amount);
         let event = last_event().unwrap();
-        let check_event = Event::pallet_erc20(crate::Event::Transfer(sender_acc, reciever_acc, amount));
+        let check_event = Event::pallet_erc20(crate::Event::Transfer {
+            asset_id: ASSET_ID,
+            from: sender_acc,
+            to: reciever_acc,
+            amount,
+            from_balance: sender_bal_before - amount,
+            to_balance: reciever_bal_before + amount,
+        });
 
         assert_ok!(transfer_result, ().into());
-        assert_eq!(sender_bal_before - amount, PalletErc20::get_balance(sender_acc));
-        assert_eq!(reciever_bal_before + amount, PalletErc20::get_balance(reciever_acc));
+        assert_eq!(sender_bal_before - amount, PalletErc20::get_balance(ASSET_ID, sender_acc));
+        assert_eq!(reciever_bal_before + amount, PalletErc20::get_balance(ASSET_ID, reciever_acc));
         assert_eq!(check_event, event);
     });
 }
@@ -42,14 +63,14 @@ fn it_fails_transfer_token_insufficient_balance() {
         let sender_acc = BALANCES[2].0;
         let reciever_acc = BALANCES[3].0;
 
-        let sender_bal_before = PalletErc20::get_balance(sender_acc);
+        let sender_bal_before = PalletErc20::get_balance(ASSET_ID, sender_acc);
         let amount = 1 + sender_bal_before;
-        let reciever_bal_before = PalletErc20::get_balance(reciever_acc);
-        let transfer_result = PalletErc20::transfer(Origin::signed(sender_acc), reciever_acc, amount);
+        let reciever_bal_before = PalletErc20::get_balance(ASSET_ID, reciever_acc);
+        let transfer_result = PalletErc20::transfer(Origin::signed(sender_acc), ASSET_ID, reciever_acc, amount);
 
         assert_noop!(transfer_result, RuntimeError::TransferAmountExceedsBalance);
-        assert_eq!(sender_bal_before, PalletErc20::get_balance(sender_acc));
-        assert_eq!(reciever_bal_before, PalletErc20::get_balance(reciever_acc));
+        assert_eq!(sender_bal_before, PalletErc20::get_balance(ASSET_ID, sender_acc));
+        assert_eq!(reciever_bal_before, PalletErc20::get_balance(ASSET_ID, reciever_acc));
     });
 }
 
@@ -61,18 +82,28 @@ fn it_works_change_allowance() {
         let value = 5000;
         let derc_value = 1000;
 
-        let alow_before = PalletErc20::get_allowance(donor_acc, sender_acc);
-        let incr_allow_res = PalletErc20::increase_allowance(Origin::signed(donor_acc), sender_acc, value);
-        let alow_after_inc = PalletErc20::get_allowance(donor_acc, sender_acc);
+        let alow_before = PalletErc20::get_allowance((ASSET_ID, donor_acc), sender_acc);
+        let incr_allow_res = PalletErc20::increase_allowance(Origin::signed(donor_acc), ASSET_ID, sender_acc, value);
+        let alow_after_inc = PalletErc20::get_allowance((ASSET_ID, donor_acc), sender_acc);
 
         let event_after_incr = last_event().unwrap();
-        let check_event_after_incr  = Event::pallet_erc20(crate::Event::Approval(donor_acc, sender_acc, value));
+        let check_event_after_incr  = Event::pallet_erc20(crate::Event::Approval {
+            asset_id: ASSET_ID,
+            owner: donor_acc,
+            spender: sender_acc,
+            amount: value,
+        });
 
-        let decr_allow_res = PalletErc20::decrease_allowance(Origin::signed(donor_acc), sender_acc, derc_value);
-        let alow_after_dec = PalletErc20::get_allowance(donor_acc, sender_acc);
+        let decr_allow_res = PalletErc20::decrease_allowance(Origin::signed(donor_acc), ASSET_ID, sender_acc, derc_value);
+        let alow_after_dec = PalletErc20::get_allowance((ASSET_ID, donor_acc), sender_acc);
 
         let event_after_decr = last_event().unwrap();
-        let check_event_after_decr  = Event::pallet_erc20(crate::Event::Approval(donor_acc, sender_acc, value - derc_value));
+        let check_event_after_decr  = Event::pallet_erc20(crate::Event::Approval {
+            asset_id: ASSET_ID,
+            owner: donor_acc,
+            spender: sender_acc,
+            amount: value - derc_value,
+        });
 
         assert_ok!(incr_allow_res, ().into());
         assert_ok!(decr_allow_res, ().into());
@@ -92,14 +123,14 @@ fn it_fails_decrease_allowance() {
         let value = 5000;
 
         // try to increase to invorrect value
-        let alow_before = PalletErc20::get_allowance(donor_acc, sender_acc);
+        let alow_before = PalletErc20::get_allowance((ASSET_ID, donor_acc), sender_acc);
         // increase to correct value
-        let _ = PalletErc20::increase_allowance(Origin::signed(donor_acc), sender_acc, value);
-        let alow_after_inc = PalletErc20::get_allowance(donor_acc, sender_acc);
+        let _ = PalletErc20::increase_allowance(Origin::signed(donor_acc), ASSET_ID, sender_acc, value);
+        let alow_after_inc = PalletErc20::get_allowance((ASSET_ID, donor_acc), sender_acc);
 
         // try to decrease to incorrect value
-        let decr_allow_incorrect_res = PalletErc20::decrease_allowance(Origin::signed(donor_acc), sender_acc, value + 1);
-        let alow_after_dec = PalletErc20::get_allowance(donor_acc, sender_acc);
+        let decr_allow_incorrect_res = PalletErc20::decrease_allowance(Origin::signed(donor_acc), ASSET_ID, sender_acc, value + 1);
+        let alow_after_dec = PalletErc20::get_allowance((ASSET_ID, donor_acc), sender_acc);
 
         assert_noop!(decr_allow_incorrect_res, RuntimeError::DecreasedAllowanceBelowZero);
         assert_eq!(0, alow_before);
@@ -118,14 +149,14 @@ fn it_works_transfer_from() {
         let value = 5000;
         let transfer_value = 3000;
 
-        let _ = PalletErc20::increase_allowance(Origin::signed(donor_acc), sender_acc, value);
-        let alow_after_inc = PalletErc20::get_allowance(donor_acc, sender_acc);
+        let _ = PalletErc20::increase_allowance(Origin::signed(donor_acc), ASSET_ID, sender_acc, value);
+        let alow_after_inc = PalletErc20::get_allowance((ASSET_ID, donor_acc), sender_acc);
 
-        let transfer_from_result = PalletErc20::transfer_from(Origin::signed(sender_acc), donor_acc, reciever_acc, transfer_value);
-        let allow_after_transfer = PalletErc20::get_allowance(donor_acc, sender_acc);
+        let transfer_from_result = PalletErc20::transfer_from(Origin::signed(sender_acc), ASSET_ID, donor_acc, reciever_acc, transfer_value);
+        let allow_after_transfer = PalletErc20::get_allowance((ASSET_ID, donor_acc), sender_acc);
 
         assert_ok!(transfer_from_result, ().into());
-        assert_eq!(transfer_value, PalletErc20::get_balance(reciever_acc));
+        assert_eq!(transfer_value, PalletErc20::get_balance(ASSET_ID, reciever_acc));
         assert_eq!(value, alow_after_inc);
         assert_eq!(value - transfer_value, allow_after_transfer);
     });
@@ -139,15 +170,15 @@ fn it_fails_transfer_from() {
         let donor_acc = BALANCES[0].0;
         let value = 5000;
 
-        let _ = PalletErc20::increase_allowance(Origin::signed(donor_acc), sender_acc, value);
-        let alow_after_inc = PalletErc20::get_allowance(donor_acc, sender_acc);
+        let _ = PalletErc20::increase_allowance(Origin::signed(donor_acc), ASSET_ID, sender_acc, value);
+        let alow_after_inc = PalletErc20::get_allowance((ASSET_ID, donor_acc), sender_acc);
 
         // FAILS when not enough allowance
-        let transfer_from_result = PalletErc20::transfer_from(Origin::signed(sender_acc), donor_acc, reciever_acc, value + 1);
-        let allow_after_transfer = PalletErc20::get_allowance(donor_acc, sender_acc);
+        let transfer_from_result = PalletErc20::transfer_from(Origin::signed(sender_acc), ASSET_ID, donor_acc, reciever_acc, value + 1);
+        let allow_after_transfer = PalletErc20::get_allowance((ASSET_ID, donor_acc), sender_acc);
 
-        let _ = PalletErc20::increase_allowance(Origin::signed(donor_acc), sender_acc, BALANCES[0].1 + 500);
-        let transfer_from_result_over_bal = PalletErc20::transfer_from(Origin::signed(sender_acc), donor_acc, reciever_acc, BALANCES[0].1 + 1);
+        let _ = PalletErc20::increase_allowance(Origin::signed(donor_acc), ASSET_ID, sender_acc, BALANCES[0].1 + 500);
+        let transfer_from_result_over_bal = PalletErc20::transfer_from(Origin::signed(sender_acc), ASSET_ID, donor_acc, reciever_acc, BALANCES[0].1 + 1);
 
         assert_noop!(transfer_from_result, RuntimeError::InsufficientAllowance);
         assert_noop!(transfer_from_result_over_bal, RuntimeError::TransferAmountExceedsBalance);
@@ -155,6 +186,33 @@ fn it_fails_transfer_from() {
     });
 }
 
+#[test]
+fn it_fails_transfer_from_atomically() {
+    new_test_ext().execute_with(|| {
+        let spender_acc = BALANCES[2].0;
+        let reciever_acc = BALANCES[3].0;
+        let donor_acc = BALANCES[0].0;
+        let value = 5000;
+
+        let _ = PalletErc20::approve(Origin::signed(donor_acc), ASSET_ID, spender_acc, value);
+        let _ = PalletErc20::freeze_account(Origin::root(), ASSET_ID, reciever_acc);
+        let frozen_result = PalletErc20::transfer_from(Origin::signed(spender_acc), ASSET_ID, donor_acc, reciever_acc, 3000);
+        let allowance_after_frozen = PalletErc20::get_allowance((ASSET_ID, donor_acc), spender_acc);
+
+        let _ = PalletErc20::thaw_account(Origin::root(), ASSET_ID, reciever_acc);
+        let _ = PalletErc20::approve(Origin::signed(donor_acc), ASSET_ID, spender_acc, BALANCES[0].1 + 1);
+        let over_balance_result = PalletErc20::transfer_from(Origin::signed(spender_acc), ASSET_ID, donor_acc, reciever_acc, BALANCES[0].1 + 1);
+        let allowance_after_over_balance = PalletErc20::get_allowance((ASSET_ID, donor_acc), spender_acc);
+
+        assert_eq!(Some(RuntimeError::AccountFrozen.into()), frozen_result.err().map(|e| e.error));
+        assert_eq!(value, allowance_after_frozen);
+        assert_eq!(Some(RuntimeError::TransferAmountExceedsBalance.into()), over_balance_result.err().map(|e| e.error));
+        assert_eq!(BALANCES[0].1 + 1, allowance_after_over_balance);
+        assert_eq!(BALANCES[0].1, PalletErc20::get_balance(ASSET_ID, donor_acc));
+        assert_eq!(BALANCES[3].1, PalletErc20::get_balance(ASSET_ID, reciever_acc));
+    });
+}
+
 
 #[test]
 fn it_works_mint_tokens() {
@@ -162,12 +220,12 @@ fn it_works_mint_tokens() {
         let sender_acc = BALANCES[3].0;
         let mint_amount = 500;
 
-        let total_supply_before = PalletErc20::get_total_supply();
-        let mint_call = PalletErc20::_mint(sender_acc, mint_amount);
+        let total_supply_before = PalletErc20::get_total_supply(ASSET_ID);
+        let mint_call = PalletErc20::_mint(ASSET_ID, sender_acc, mint_amount);
 
         assert_ok!(mint_call, ().into());
-        assert_eq!(BALANCES[3].1 + mint_amount, PalletErc20::get_balance(sender_acc));
-        assert_eq!(total_supply_before + mint_amount, PalletErc20::get_total_supply());
+        assert_eq!(BALANCES[3].1 + mint_amount, PalletErc20::get_balance(ASSET_ID, sender_acc));
+        assert_eq!(total_supply_before + mint_amount, PalletErc20::get_total_supply(ASSET_ID));
     });
 }
 
@@ -177,13 +235,1776 @@ fn it_works_burn_tokens() {
         let sender_acc = BALANCES[2].0;
         let burn_amount = 500;
 
-        let failed_burn_call = PalletErc20::_burn(sender_acc, BALANCES[2].1 + 1);
-        let total_supply_before = PalletErc20::get_total_supply();
-        let burn_call = PalletErc20::_burn(sender_acc, burn_amount);
+        let failed_burn_call = PalletErc20::_burn(ASSET_ID, sender_acc, BALANCES[2].1 + 1);
+        let total_supply_before = PalletErc20::get_total_supply(ASSET_ID);
+        let burn_call = PalletErc20::_burn(ASSET_ID, sender_acc, burn_amount);
+
+        assert_noop!(failed_burn_call, RuntimeError::BurnAmountExceedsBalance);
+        assert_ok!(burn_call, ().into());
+        assert_eq!(BALANCES[2].1 - burn_amount, PalletErc20::get_balance(ASSET_ID, sender_acc));
+        assert_eq!(total_supply_before - burn_amount, PalletErc20::get_total_supply(ASSET_ID));
+    });
+}
+#[test]
+fn it_works_mint_call() {
+    new_test_ext().execute_with(|| {
+        let reciever_acc = BALANCES[3].0;
+        let mint_amount = 500;
+
+        let total_supply_before = PalletErc20::get_total_supply(ASSET_ID);
+        let mint_call = PalletErc20::mint(Origin::root(), ASSET_ID, reciever_acc, mint_amount);
+
+        assert_ok!(mint_call, ().into());
+        assert_eq!(Ok(Event::pallet_erc20(crate::Event::Minted { asset_id: ASSET_ID, to: reciever_acc, amount: mint_amount })), last_event());
+        assert_eq!(BALANCES[3].1 + mint_amount, PalletErc20::get_balance(ASSET_ID, reciever_acc));
+        assert_eq!(total_supply_before + mint_amount, PalletErc20::get_total_supply(ASSET_ID));
+    });
+}
+
+#[test]
+fn it_fails_mint_call_bad_origin() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = BALANCES[0].0;
+        let reciever_acc = BALANCES[3].0;
+
+        let total_supply_before = PalletErc20::get_total_supply(ASSET_ID);
+        let mint_call = PalletErc20::mint(Origin::signed(sender_acc), ASSET_ID, reciever_acc, 500);
+
+        assert_noop!(mint_call, DispatchError::BadOrigin);
+        assert_eq!(BALANCES[3].1, PalletErc20::get_balance(ASSET_ID, reciever_acc));
+        assert_eq!(total_supply_before, PalletErc20::get_total_supply(ASSET_ID));
+    });
+}
+
+#[test]
+fn it_works_burn_call() {
+    new_test_ext().execute_with(|| {
+        let holder_acc = BALANCES[2].0;
+        let burn_amount = 500;
+
+        let total_supply_before = PalletErc20::get_total_supply(ASSET_ID);
+        let failed_burn_call = PalletErc20::burn(Origin::root(), ASSET_ID, holder_acc, BALANCES[2].1 + 1);
+        let burn_call = PalletErc20::burn(Origin::root(), ASSET_ID, holder_acc, burn_amount);
 
         assert_noop!(failed_burn_call, RuntimeError::BurnAmountExceedsBalance);
         assert_ok!(burn_call, ().into());
-        assert_eq!(BALANCES[2].1 - burn_amount, PalletErc20::get_balance(sender_acc));
-        assert_eq!(total_supply_before - burn_amount, PalletErc20::get_total_supply());
+        assert_eq!(Ok(Event::pallet_erc20(crate::Event::Burned { asset_id: ASSET_ID, from: holder_acc, amount: burn_amount })), last_event());
+        assert_eq!(BALANCES[2].1 - burn_amount, PalletErc20::get_balance(ASSET_ID, holder_acc));
+        assert_eq!(total_supply_before - burn_amount, PalletErc20::get_total_supply(ASSET_ID));
+    });
+}
+
+#[test]
+fn it_fails_burn_locked_tokens() {
+    new_test_ext().execute_with(|| {
+        let holder_acc = BALANCES[0].0;
+        let (vesting_acc, _, _, _, locked) = SECOND_VESTING;
+        <GenesisToken as LockableCurrency<_>>::set_lock(*b"staking ", &holder_acc, 1000, WithdrawReasons::TRANSFER);
+
+        let locked_burn_result = PalletErc20::burn(Origin::root(), ASSET_ID, holder_acc, BALANCES[0].1 - 999);
+        let vesting_burn_result = SecondErc20::burn_self(Origin::signed(vesting_acc), ASSET_ID, SECOND_BALANCES[1].1 - locked + 1);
+        let unlocked_burn_result = PalletErc20::burn(Origin::root(), ASSET_ID, holder_acc, BALANCES[0].1 - 1000);
+
+        assert_noop!(locked_burn_result, RuntimeError::LiquidityRestrictions);
+        assert_noop!(vesting_burn_result, Error::<Test, Instance1>::LockedByVesting);
+        assert_ok!(unlocked_burn_result, ().into());
+        assert_eq!(1000, PalletErc20::get_balance(ASSET_ID, holder_acc));
+    });
+}
+
+#[test]
+fn it_fails_burn_call_bad_origin() {
+    new_test_ext().execute_with(|| {
+        let holder_acc = BALANCES[2].0;
+
+        let total_supply_before = PalletErc20::get_total_supply(ASSET_ID);
+        let burn_call = PalletErc20::burn(Origin::signed(holder_acc), ASSET_ID, holder_acc, 500);
+
+        assert_noop!(burn_call, DispatchError::BadOrigin);
+        assert_eq!(BALANCES[2].1, PalletErc20::get_balance(ASSET_ID, holder_acc));
+        assert_eq!(total_supply_before, PalletErc20::get_total_supply(ASSET_ID));
+    });
+}
+
+#[test]
+fn it_works_burn_self() {
+    new_test_ext().execute_with(|| {
+        let holder_acc = BALANCES[1].0;
+        let burn_amount = 1000;
+
+        let total_supply_before = PalletErc20::get_total_supply(ASSET_ID);
+        let burn_call = PalletErc20::burn_self(Origin::signed(holder_acc), ASSET_ID, burn_amount);
+        let failed_burn_call = PalletErc20::burn_self(Origin::signed(holder_acc), ASSET_ID, BALANCES[1].1);
+
+        assert_ok!(burn_call, ().into());
+        assert_noop!(failed_burn_call, RuntimeError::BurnAmountExceedsBalance);
+        assert_eq!(BALANCES[1].1 - burn_amount, PalletErc20::get_balance(ASSET_ID, holder_acc));
+        assert_eq!(total_supply_before - burn_amount, PalletErc20::get_total_supply(ASSET_ID));
+    });
+}
+
+#[test]
+fn it_works_create_token() {
+    new_test_ext().execute_with(|| {
+        let owner_acc = BALANCES[2].0;
+        let initial_supply = 10_000;
+        let name = b"SecondTestToken".to_vec();
+        let sym = b"STT2".to_vec();
+
+        let asset_id = PalletErc20::get_next_asset_id();
+        let create_result = PalletErc20::create_token(Origin::root(), owner_acc, name.clone(), sym.clone(), 6, initial_supply);
+
+        assert_ok!(create_result, ().into());
+        assert_eq!(asset_id + 1, PalletErc20::get_next_asset_id());
+        assert_eq!(Some(owner_acc), PalletErc20::get_owner(asset_id));
+        assert_eq!(name, PalletErc20::get_name(asset_id));
+        assert_eq!(sym, PalletErc20::get_symbol(asset_id));
+        assert_eq!(6, PalletErc20::get_decimals(asset_id));
+        assert_eq!(initial_supply, PalletErc20::get_total_supply(asset_id));
+        assert_eq!(initial_supply, PalletErc20::get_balance(asset_id, owner_acc));
+        // genesis token is untouched
+        assert_eq!(BALANCES[2].1, PalletErc20::get_balance(ASSET_ID, owner_acc));
+        assert_eq!(get_test_total_supply(), PalletErc20::get_total_supply(ASSET_ID));
+    });
+}
+
+#[test]
+fn it_fails_create_token_bad_origin() {
+    new_test_ext().execute_with(|| {
+        let owner_acc = BALANCES[2].0;
+        let asset_id = PalletErc20::get_next_asset_id();
+
+        let create_result = PalletErc20::create_token(Origin::signed(owner_acc), owner_acc, b"T".to_vec(), b"T".to_vec(), 18, 1000);
+
+        assert_noop!(create_result, DispatchError::BadOrigin);
+        assert_eq!(asset_id, PalletErc20::get_next_asset_id());
+        assert_eq!(None, PalletErc20::get_owner(asset_id));
+    });
+}
+
+#[test]
+fn it_works_transfer_created_token() {
+    new_test_ext().execute_with(|| {
+        let owner_acc = BALANCES[2].0;
+        let reciever_acc = BALANCES[3].0;
+        let amount = 700;
+
+        let asset_id = PalletErc20::get_next_asset_id();
+        let _ = PalletErc20::create_token(Origin::root(), owner_acc, b"T".to_vec(), b"T".to_vec(), 18, 1000);
+        let transfer_result = PalletErc20::transfer(Origin::signed(owner_acc), asset_id, reciever_acc, amount);
+        let event = last_event().unwrap();
+        let check_event = Event::pallet_erc20(crate::Event::Transfer {
+            asset_id,
+            from: owner_acc,
+            to: reciever_acc,
+            amount,
+            from_balance: 1000 - amount,
+            to_balance: amount,
+        });
+
+        assert_ok!(transfer_result, ().into());
+        assert_eq!(check_event, event);
+        assert_eq!(1000 - amount, PalletErc20::get_balance(asset_id, owner_acc));
+        assert_eq!(amount, PalletErc20::get_balance(asset_id, reciever_acc));
+        assert_eq!(BALANCES[3].1, PalletErc20::get_balance(ASSET_ID, reciever_acc));
+    });
+}
+
+#[test]
+fn it_fails_unknown_asset() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = BALANCES[0].0;
+        let reciever_acc = BALANCES[3].0;
+        let unknown_asset = PalletErc20::get_next_asset_id();
+
+        let transfer_result = PalletErc20::transfer(Origin::signed(sender_acc), unknown_asset, reciever_acc, 0);
+        let approve_result = PalletErc20::approve(Origin::signed(sender_acc), unknown_asset, reciever_acc, 100);
+        let mint_result = PalletErc20::mint(Origin::root(), unknown_asset, reciever_acc, 100);
+        let decrease_result = PalletErc20::decrease_allowance(Origin::signed(sender_acc), unknown_asset, reciever_acc, 0);
+
+        assert_noop!(transfer_result, RuntimeError::UnknownAsset);
+        assert_noop!(approve_result, RuntimeError::UnknownAsset);
+        assert_noop!(mint_result, RuntimeError::UnknownAsset);
+        assert_noop!(decrease_result, RuntimeError::UnknownAsset);
+    });
+}
+
+#[test]
+fn it_works_for_instances_build_genesis() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(get_second_total_supply(), SecondErc20::get_total_supply(ASSET_ID));
+        SECOND_BALANCES.iter().for_each(|(acc, bal)|{
+                assert_eq!(*bal, SecondErc20::get_balance(ASSET_ID, acc));
+        });
+        assert_eq!(get_second_token_name(), SecondErc20::get_name(ASSET_ID));
+        assert_eq!(get_second_token_sym(), SecondErc20::get_symbol(ASSET_ID));
+        assert_eq!(SECOND_DECIMALS, SecondErc20::get_decimals(ASSET_ID));
+        assert_eq!(get_test_token_name(), PalletErc20::get_name(ASSET_ID));
+        assert_eq!(get_test_total_supply(), PalletErc20::get_total_supply(ASSET_ID));
+    });
+}
+
+#[test]
+fn it_works_instances_storage_isolation() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = SECOND_BALANCES[0].0;
+        let reciever_acc = SECOND_BALANCES[1].0;
+        let amount = 2_000;
+
+        let first_sender_bal_before = PalletErc20::get_balance(ASSET_ID, sender_acc);
+        let first_reciever_bal_before = PalletErc20::get_balance(ASSET_ID, reciever_acc);
+        let transfer_result = SecondErc20::transfer(Origin::signed(sender_acc), ASSET_ID, reciever_acc, amount);
+        let event = last_event().unwrap();
+        let check_event = Event::pallet_erc20_Instance1(
+            crate::Event::<Test, Instance1>::Transfer {
+                asset_id: ASSET_ID,
+                from: sender_acc,
+                to: reciever_acc,
+                amount,
+                from_balance: SECOND_BALANCES[0].1 - amount,
+                to_balance: SECOND_BALANCES[1].1 + amount,
+            }
+        );
+
+        assert_ok!(transfer_result, ().into());
+        assert_eq!(check_event, event);
+        assert_eq!(SECOND_BALANCES[0].1 - amount, SecondErc20::get_balance(ASSET_ID, sender_acc));
+        assert_eq!(SECOND_BALANCES[1].1 + amount, SecondErc20::get_balance(ASSET_ID, reciever_acc));
+        assert_eq!(first_sender_bal_before, PalletErc20::get_balance(ASSET_ID, sender_acc));
+        assert_eq!(first_reciever_bal_before, PalletErc20::get_balance(ASSET_ID, reciever_acc));
+
+        let mint_result = PalletErc20::mint(Origin::root(), ASSET_ID, reciever_acc, amount);
+
+        assert_ok!(mint_result, ().into());
+        assert_eq!(get_test_total_supply() + amount, PalletErc20::get_total_supply(ASSET_ID));
+        assert_eq!(get_second_total_supply(), SecondErc20::get_total_supply(ASSET_ID));
+    });
+}
+
+#[test]
+fn it_works_currency_inspect() {
+    new_test_ext().execute_with(|| {
+        let holder_acc = BALANCES[2].0;
+
+        assert_eq!(get_test_total_supply(), <GenesisToken as Currency<_>>::total_issuance());
+        assert_eq!(BALANCES[2].1, <GenesisToken as Currency<_>>::free_balance(&holder_acc));
+        assert_eq!(BALANCES[2].1, <GenesisToken as Currency<_>>::total_balance(&holder_acc));
+        assert_eq!(1, <GenesisToken as Currency<_>>::minimum_balance());
+        assert_eq!(BALANCES[2].1 - 1, PalletErc20::reducible_balance(ASSET_ID, &holder_acc, true));
+        assert_eq!(BALANCES[2].1, PalletErc20::reducible_balance(ASSET_ID, &holder_acc, false));
+        assert!(!<GenesisToken as Currency<_>>::can_slash(&holder_acc, BALANCES[2].1 + 1));
+    });
+}
+
+#[test]
+fn it_works_currency_transfer() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = BALANCES[0].0;
+        let reciever_acc = BALANCES[3].0;
+        let amount = 1000;
+
+        let transfer_result = <GenesisToken as Currency<_>>::transfer(
+            &sender_acc, &reciever_acc, amount, ExistenceRequirement::AllowDeath
+        );
+
+        assert_ok!(transfer_result);
+        assert_eq!(BALANCES[0].1 - amount, PalletErc20::get_balance(ASSET_ID, sender_acc));
+        assert_eq!(amount, PalletErc20::get_balance(ASSET_ID, reciever_acc));
+        assert_eq!(get_test_total_supply(), PalletErc20::get_total_supply(ASSET_ID));
+        assert_noop!(
+            <GenesisToken as Currency<_>>::transfer(&reciever_acc, &sender_acc, amount + 1, ExistenceRequirement::AllowDeath),
+            RuntimeError::TransferAmountExceedsBalance
+        );
+    });
+}
+
+#[test]
+fn it_works_currency_make_free_balance_be() {
+    new_test_ext().execute_with(|| {
+        let holder_acc = BALANCES[3].0;
+
+        let imbalance = <GenesisToken as Currency<_>>::make_free_balance_be(&holder_acc, 42);
+        drop(imbalance);
+
+        assert_eq!(42, PalletErc20::get_balance(ASSET_ID, holder_acc));
+        assert_eq!(get_test_total_supply() + 42, PalletErc20::get_total_supply(ASSET_ID));
+    });
+}
+
+#[test]
+fn it_works_fee_payment_in_tokens() {
+    new_test_ext().execute_with(|| {
+        let payer_acc = BALANCES[2].0;
+        let call = Call::System(frame_system::Call::remark(vec![]));
+        let info = DispatchInfo::default();
+        let post_info = PostDispatchInfo { actual_weight: None, pays_fee: Pays::Yes };
+        let fee = 400;
+        let corrected_fee = 100;
+
+        let total_supply_before = PalletErc20::get_total_supply(ASSET_ID);
+        let withdrawn = <FeeAdapter as OnChargeTransaction<Test>>::withdraw_fee(&payer_acc, &call, &info, fee, 0);
+
+        assert_eq!(Ok(Some(fee / 2)), withdrawn);
+        assert_eq!(BALANCES[2].1 - fee / 2, PalletErc20::get_balance(ASSET_ID, payer_acc));
+
+        let corrected = <FeeAdapter as OnChargeTransaction<Test>>::correct_and_deposit_fee(
+            &payer_acc, &info, &post_info, corrected_fee, 0, withdrawn.unwrap()
+        );
+
+        assert_eq!(Ok(()), corrected);
+        assert_eq!(BALANCES[2].1 - corrected_fee / 2, PalletErc20::get_balance(ASSET_ID, payer_acc));
+        assert_eq!(total_supply_before - corrected_fee / 2, PalletErc20::get_total_supply(ASSET_ID));
+    });
+}
+
+#[test]
+fn it_fails_fee_payment_insufficient_tokens() {
+    new_test_ext().execute_with(|| {
+        let payer_acc = BALANCES[3].0;
+        let call = Call::System(frame_system::Call::remark(vec![]));
+        let info = DispatchInfo::default();
+
+        let withdrawn = <FeeAdapter as OnChargeTransaction<Test>>::withdraw_fee(&payer_acc, &call, &info, 400, 0);
+
+        assert_eq!(Err(TransactionValidityError::Invalid(InvalidTransaction::Payment)), withdrawn);
+        assert_eq!(get_test_total_supply(), PalletErc20::get_total_supply(ASSET_ID));
+    });
+}
+
+#[test]
+fn it_fails_fee_payment_from_locked_tokens() {
+    new_test_ext().execute_with(|| {
+        let payer_acc = BALANCES[2].0;
+        let call = Call::System(frame_system::Call::remark(vec![]));
+        let info = DispatchInfo::default();
+        let fee = 400;
+        <GenesisToken as LockableCurrency<_>>::set_lock(*b"staking ", &payer_acc, BALANCES[2].1, WithdrawReasons::TRANSFER);
+
+        let transfer_locked_result = <FeeAdapter as OnChargeTransaction<Test>>::withdraw_fee(&payer_acc, &call, &info, fee, 0);
+
+        assert_eq!(Ok(Some(fee / 2)), transfer_locked_result);
+
+        <GenesisToken as LockableCurrency<_>>::set_lock(*b"fees    ", &payer_acc, BALANCES[2].1, WithdrawReasons::TRANSACTION_PAYMENT);
+        let fee_locked_result = <FeeAdapter as OnChargeTransaction<Test>>::withdraw_fee(&payer_acc, &call, &info, fee, 0);
+
+        assert_eq!(Err(TransactionValidityError::Invalid(InvalidTransaction::Payment)), fee_locked_result);
+        assert_eq!(BALANCES[2].1 - fee / 2, PalletErc20::get_balance(ASSET_ID, payer_acc));
+    });
+}
+
+#[test]
+fn it_fails_fee_payment_leaving_dust() {
+    new_test_ext().execute_with(|| {
+        let payer_acc = BALANCES[2].0;
+        let call = Call::System(frame_system::Call::remark(vec![]));
+        let info = DispatchInfo::default();
+        let fee = 400;
+        set_minimum_balance(900);
+
+        let dust_result = <FeeAdapter as OnChargeTransaction<Test>>::withdraw_fee(&payer_acc, &call, &info, fee, 0);
+
+        assert_eq!(Err(TransactionValidityError::Invalid(InvalidTransaction::Payment)), dust_result);
+        assert_eq!(BALANCES[2].1, PalletErc20::get_balance(ASSET_ID, payer_acc));
+
+        set_minimum_balance(BALANCES[2].1 - fee / 2);
+        let above_minimum_result = <FeeAdapter as OnChargeTransaction<Test>>::withdraw_fee(&payer_acc, &call, &info, fee, 0);
+
+        assert_eq!(Ok(Some(fee / 2)), above_minimum_result);
+        assert_eq!(BALANCES[2].1 - fee / 2, PalletErc20::get_balance(ASSET_ID, payer_acc));
+    });
+}
+
+#[test]
+fn it_works_set_metadata() {
+    new_test_ext().execute_with(|| {
+        let name = b"RebrandedToken".to_vec();
+        let sym = b"RBT".to_vec();
+
+        let set_result = PalletErc20::set_metadata(Origin::root(), ASSET_ID, name.clone(), sym.clone(), 9);
+        let event = last_event().unwrap();
+        let check_event = Event::pallet_erc20(crate::Event::MetadataSet { asset_id: ASSET_ID, name: name.clone(), symbol: sym.clone(), decimals: 9 });
+
+        assert_ok!(set_result, ().into());
+        assert_eq!(check_event, event);
+        assert_eq!(name, PalletErc20::get_name(ASSET_ID));
+        assert_eq!(sym, PalletErc20::get_symbol(ASSET_ID));
+        assert_eq!(9, PalletErc20::get_decimals(ASSET_ID));
+    });
+}
+
+#[test]
+fn it_fails_set_metadata() {
+    new_test_ext().execute_with(|| {
+        let too_long = vec![b'A'; StringLimit::get() as usize + 1];
+        let unknown_asset = PalletErc20::get_next_asset_id();
+
+        let bad_origin_result = PalletErc20::set_metadata(Origin::signed(BALANCES[0].0), ASSET_ID, b"N".to_vec(), b"S".to_vec(), 9);
+        let long_name_result = PalletErc20::set_metadata(Origin::root(), ASSET_ID, too_long.clone(), b"S".to_vec(), 9);
+        let long_sym_result = PalletErc20::set_metadata(Origin::root(), ASSET_ID, b"N".to_vec(), too_long.clone(), 9);
+        let unknown_asset_result = PalletErc20::set_metadata(Origin::root(), unknown_asset, b"N".to_vec(), b"S".to_vec(), 9);
+        let create_result = PalletErc20::create_token(Origin::root(), BALANCES[0].0, too_long, b"S".to_vec(), 9, 100);
+
+        assert_noop!(bad_origin_result, DispatchError::BadOrigin);
+        assert_noop!(long_name_result, RuntimeError::BadMetadata);
+        assert_noop!(long_sym_result, RuntimeError::BadMetadata);
+        assert_noop!(unknown_asset_result, RuntimeError::UnknownAsset);
+        assert_noop!(create_result, RuntimeError::BadMetadata);
+        assert_eq!(get_test_token_name(), PalletErc20::get_name(ASSET_ID));
+        assert_eq!(DEFAULT_DECIMALS, PalletErc20::get_decimals(ASSET_ID));
+    });
+}
+
+fn sign_permit(signer: AccountId, owner: AccountId, spender: AccountId, value: Balance, deadline: u64) -> TestSignature {
+    let nonce = PalletErc20::get_permit_nonce(owner);
+    TestSignature(signer, PalletErc20::permit_payload(ASSET_ID, &owner, &spender, value, nonce, deadline))
+}
+
+#[test]
+fn it_works_permit() {
+    new_test_ext().execute_with(|| {
+        let owner_acc = BALANCES[0].0;
+        let spender_acc = BALANCES[2].0;
+        let relayer_acc = BALANCES[3].0;
+        let value = 5000;
+        let deadline = 10;
+
+        let signature = sign_permit(owner_acc, owner_acc, spender_acc, value, deadline);
+        let permit_result = PalletErc20::permit(Origin::signed(relayer_acc), ASSET_ID, owner_acc, spender_acc, value, deadline, signature);
+        let event = last_event().unwrap();
+        let check_event = Event::pallet_erc20(crate::Event::Approval { asset_id: ASSET_ID, owner: owner_acc, spender: spender_acc, amount: value });
+
+        assert_ok!(permit_result, ().into());
+        assert_eq!(check_event, event);
+        assert_eq!(value, PalletErc20::get_allowance((ASSET_ID, owner_acc), spender_acc));
+        assert_eq!(1, PalletErc20::get_permit_nonce(owner_acc));
+    });
+}
+
+#[test]
+fn it_fails_permit_replay() {
+    new_test_ext().execute_with(|| {
+        let owner_acc = BALANCES[0].0;
+        let spender_acc = BALANCES[2].0;
+        let relayer_acc = BALANCES[3].0;
+        let value = 5000;
+        let deadline = 10;
+
+        let signature = sign_permit(owner_acc, owner_acc, spender_acc, value, deadline);
+        let first_result = PalletErc20::permit(Origin::signed(relayer_acc), ASSET_ID, owner_acc, spender_acc, value, deadline, signature.clone());
+        let _ = PalletErc20::transfer_from(Origin::signed(spender_acc), ASSET_ID, owner_acc, spender_acc, value);
+        let replay_result = PalletErc20::permit(Origin::signed(relayer_acc), ASSET_ID, owner_acc, spender_acc, value, deadline, signature);
+
+        assert_ok!(first_result, ().into());
+        assert_noop!(replay_result, RuntimeError::BadPermitSignature);
+        assert_eq!(0, PalletErc20::get_allowance((ASSET_ID, owner_acc), spender_acc));
+        assert_eq!(1, PalletErc20::get_permit_nonce(owner_acc));
+    });
+}
+
+#[test]
+fn it_fails_permit_expired() {
+    new_test_ext().execute_with(|| {
+        let owner_acc = BALANCES[0].0;
+        let spender_acc = BALANCES[2].0;
+        let relayer_acc = BALANCES[3].0;
+        let deadline = 10;
+
+        let signature = sign_permit(owner_acc, owner_acc, spender_acc, 5000, deadline);
+        System::set_block_number(deadline + 1);
+        let permit_result = PalletErc20::permit(Origin::signed(relayer_acc), ASSET_ID, owner_acc, spender_acc, 5000, deadline, signature);
+
+        assert_noop!(permit_result, RuntimeError::PermitExpired);
+        assert_eq!(0, PalletErc20::get_allowance((ASSET_ID, owner_acc), spender_acc));
+        assert_eq!(0, PalletErc20::get_permit_nonce(owner_acc));
+    });
+}
+
+#[test]
+fn it_fails_permit_wrong_signer() {
+    new_test_ext().execute_with(|| {
+        let owner_acc = BALANCES[0].0;
+        let spender_acc = BALANCES[2].0;
+        let relayer_acc = BALANCES[3].0;
+        let deadline = 10;
+
+        // spender signs the permit on behalf of the owner
+        let signature = sign_permit(spender_acc, owner_acc, spender_acc, 5000, deadline);
+        let permit_result = PalletErc20::permit(Origin::signed(relayer_acc), ASSET_ID, owner_acc, spender_acc, 5000, deadline, signature);
+        // owner signs a different amount
+        let signature = sign_permit(owner_acc, owner_acc, spender_acc, 1, deadline);
+        let tampered_result = PalletErc20::permit(Origin::signed(relayer_acc), ASSET_ID, owner_acc, spender_acc, 5000, deadline, signature);
+
+        assert_noop!(permit_result, RuntimeError::BadPermitSignature);
+        assert_noop!(tampered_result, RuntimeError::BadPermitSignature);
+        assert_eq!(0, PalletErc20::get_allowance((ASSET_ID, owner_acc), spender_acc));
     });
-}
\ No newline at end of file
+}
+
+#[test]
+fn it_works_pause_and_unpause() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = BALANCES[0].0;
+        let spender_acc = BALANCES[1].0;
+        let reciever_acc = BALANCES[3].0;
+        let _ = PalletErc20::approve(Origin::signed(sender_acc), ASSET_ID, spender_acc, 1000);
+
+        let pause_result = PalletErc20::pause(Origin::root(), ASSET_ID);
+        let pause_event = last_event().unwrap();
+        let transfer_result = PalletErc20::transfer(Origin::signed(sender_acc), ASSET_ID, reciever_acc, 500);
+        let transfer_from_result = PalletErc20::transfer_from(Origin::signed(spender_acc), ASSET_ID, sender_acc, reciever_acc, 500);
+        let mint_result = PalletErc20::mint(Origin::root(), ASSET_ID, reciever_acc, 500);
+        let burn_result = PalletErc20::burn(Origin::root(), ASSET_ID, sender_acc, 500);
+
+        assert_ok!(pause_result, ().into());
+        assert_eq!(Event::pallet_erc20(crate::Event::Paused { asset_id: ASSET_ID }), pause_event);
+        assert!(PalletErc20::is_paused(ASSET_ID));
+        assert_noop!(transfer_result, RuntimeError::TokenPaused);
+        assert_noop!(transfer_from_result, RuntimeError::TokenPaused);
+        assert_noop!(mint_result, RuntimeError::TokenPaused);
+        assert_noop!(burn_result, RuntimeError::TokenPaused);
+        assert_eq!(BALANCES[0].1, PalletErc20::get_balance(ASSET_ID, sender_acc));
+        assert_eq!(1000, PalletErc20::get_allowance((ASSET_ID, sender_acc), spender_acc));
+
+        let unpause_result = PalletErc20::unpause(Origin::root(), ASSET_ID);
+        let unpause_event = last_event().unwrap();
+        let transfer_result = PalletErc20::transfer(Origin::signed(sender_acc), ASSET_ID, reciever_acc, 500);
+
+        assert_ok!(unpause_result, ().into());
+        assert_eq!(Event::pallet_erc20(crate::Event::Unpaused { asset_id: ASSET_ID }), unpause_event);
+        assert_ok!(transfer_result, ().into());
+        assert_eq!(BALANCES[3].1 + 500, PalletErc20::get_balance(ASSET_ID, reciever_acc));
+    });
+}
+
+#[test]
+fn it_works_pause_is_per_token() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = BALANCES[0].0;
+        let reciever_acc = BALANCES[3].0;
+
+        let create_result = PalletErc20::create_token(Origin::root(), sender_acc, b"OtherToken".to_vec(), b"OT".to_vec(), 6, 1000);
+        let other_asset_id = ASSET_ID + 1;
+        let pause_result = PalletErc20::pause(Origin::root(), ASSET_ID);
+        let transfer_result = PalletErc20::transfer(Origin::signed(sender_acc), other_asset_id, reciever_acc, 500);
+
+        assert_ok!(create_result, ().into());
+        assert_ok!(pause_result, ().into());
+        assert_ok!(transfer_result, ().into());
+        assert_eq!(500, PalletErc20::get_balance(other_asset_id, reciever_acc));
+    });
+}
+
+#[test]
+fn it_works_freeze_and_thaw_account() {
+    new_test_ext().execute_with(|| {
+        let frozen_acc = BALANCES[0].0;
+        let other_acc = BALANCES[1].0;
+
+        let freeze_result = PalletErc20::freeze_account(Origin::root(), ASSET_ID, frozen_acc);
+        let freeze_event = last_event().unwrap();
+        let send_result = PalletErc20::transfer(Origin::signed(frozen_acc), ASSET_ID, other_acc, 500);
+        let receive_result = PalletErc20::transfer(Origin::signed(other_acc), ASSET_ID, frozen_acc, 500);
+        let mint_result = PalletErc20::mint(Origin::root(), ASSET_ID, frozen_acc, 500);
+        let burn_result = PalletErc20::burn(Origin::root(), ASSET_ID, frozen_acc, 500);
+        let _ = PalletErc20::approve(Origin::signed(other_acc), ASSET_ID, frozen_acc, 1000);
+        let spend_result = PalletErc20::transfer_from(Origin::signed(frozen_acc), ASSET_ID, other_acc, frozen_acc, 500);
+
+        assert_ok!(freeze_result, ().into());
+        assert_eq!(Event::pallet_erc20(crate::Event::Frozen { asset_id: ASSET_ID, who: frozen_acc }), freeze_event);
+        assert!(PalletErc20::is_frozen(ASSET_ID, frozen_acc));
+        assert_noop!(send_result, RuntimeError::AccountFrozen);
+        assert_noop!(receive_result, RuntimeError::AccountFrozen);
+        assert_noop!(mint_result, RuntimeError::AccountFrozen);
+        assert_noop!(burn_result, RuntimeError::AccountFrozen);
+        assert_noop!(spend_result, RuntimeError::AccountFrozen);
+        assert_eq!(BALANCES[0].1, PalletErc20::get_balance(ASSET_ID, frozen_acc));
+
+        let thaw_result = PalletErc20::thaw_account(Origin::root(), ASSET_ID, frozen_acc);
+        let thaw_event = last_event().unwrap();
+        let send_result = PalletErc20::transfer(Origin::signed(frozen_acc), ASSET_ID, other_acc, 500);
+
+        assert_ok!(thaw_result, ().into());
+        assert_eq!(Event::pallet_erc20(crate::Event::Thawed { asset_id: ASSET_ID, who: frozen_acc }), thaw_event);
+        assert_ok!(send_result, ().into());
+        assert_eq!(BALANCES[0].1 - 500, PalletErc20::get_balance(ASSET_ID, frozen_acc));
+    });
+}
+
+#[test]
+fn it_fails_pause_and_freeze_bad_origin() {
+    new_test_ext().execute_with(|| {
+        let caller_acc = BALANCES[0].0;
+
+        let pause_result = PalletErc20::pause(Origin::signed(caller_acc), ASSET_ID);
+        let freeze_result = PalletErc20::freeze_account(Origin::signed(caller_acc), ASSET_ID, caller_acc);
+        let unknown_asset_result = PalletErc20::pause(Origin::root(), ASSET_ID + 1);
+
+        assert_noop!(pause_result, DispatchError::BadOrigin);
+        assert_noop!(freeze_result, DispatchError::BadOrigin);
+        assert_noop!(unknown_asset_result, RuntimeError::UnknownAsset);
+        assert!(!PalletErc20::is_paused(ASSET_ID));
+        assert!(!PalletErc20::is_frozen(ASSET_ID, caller_acc));
+    });
+}
+
+#[test]
+fn it_works_fee_payment_while_paused() {
+    new_test_ext().execute_with(|| {
+        let payer_acc = BALANCES[2].0;
+        let call = Call::System(frame_system::Call::remark(vec![]));
+        let info = DispatchInfo::default();
+
+        let _ = PalletErc20::pause(Origin::root(), ASSET_ID);
+        let withdrawn = <FeeAdapter as OnChargeTransaction<Test>>::withdraw_fee(&payer_acc, &call, &info, 400, 0);
+
+        assert_eq!(Ok(Some(200)), withdrawn);
+        assert_eq!(BALANCES[2].1 - 200, PalletErc20::get_balance(ASSET_ID, payer_acc));
+    });
+}
+
+#[test]
+fn it_works_genesis_cap() {
+    new_test_ext().execute_with(|| {
+        let reciever_acc = SECOND_BALANCES[0].0;
+        let headroom = SECOND_CAP - get_second_total_supply();
+
+        let over_cap_result = SecondErc20::mint(Origin::root(), ASSET_ID, reciever_acc, headroom + 1);
+        let mint_result = SecondErc20::mint(Origin::root(), ASSET_ID, reciever_acc, headroom);
+
+        assert_eq!(None, PalletErc20::get_cap(ASSET_ID));
+        assert_eq!(Some(SECOND_CAP), SecondErc20::get_cap(ASSET_ID));
+        assert_noop!(over_cap_result, Error::<Test, Instance1>::CapExceeded);
+        assert_ok!(mint_result, ().into());
+        assert_eq!(SECOND_CAP, SecondErc20::get_total_supply(ASSET_ID));
+    });
+}
+
+#[test]
+#[should_panic(expected = "Genesis build failed: Total supply exceeds cap")]
+fn it_fails_genesis_supply_above_cap() {
+    let _ = crate::GenesisConfig::<Test> {
+        phantom: Default::default(),
+        balances: BALANCES.iter().map(|(x, y)| (*x, *y)).collect(),
+        name: get_test_token_name(),
+        sym: get_test_token_sym(),
+        decimals: DEFAULT_DECIMALS,
+        cap: Some(get_test_total_supply() - 1),
+        vesting: vec![],
+    }
+    .build_storage();
+}
+
+#[test]
+fn it_works_set_cap() {
+    new_test_ext().execute_with(|| {
+        let reciever_acc = BALANCES[3].0;
+        let total_supply = get_test_total_supply();
+
+        let set_cap_result = PalletErc20::set_cap(Origin::root(), ASSET_ID, Some(total_supply + 500));
+        let event = last_event().unwrap();
+        let over_cap_result = PalletErc20::mint(Origin::root(), ASSET_ID, reciever_acc, 501);
+        let mint_result = PalletErc20::mint(Origin::root(), ASSET_ID, reciever_acc, 500);
+
+        assert_ok!(set_cap_result, ().into());
+        assert_eq!(Event::pallet_erc20(crate::Event::CapSet { asset_id: ASSET_ID, cap: Some(total_supply + 500) }), event);
+        assert_noop!(over_cap_result, RuntimeError::CapExceeded);
+        assert_ok!(mint_result, ().into());
+        assert_eq!(total_supply + 500, PalletErc20::get_total_supply(ASSET_ID));
+
+        let remove_cap_result = PalletErc20::set_cap(Origin::root(), ASSET_ID, None);
+        let mint_result = PalletErc20::mint(Origin::root(), ASSET_ID, reciever_acc, 500);
+
+        assert_ok!(remove_cap_result, ().into());
+        assert_ok!(mint_result, ().into());
+        assert_eq!(None, PalletErc20::get_cap(ASSET_ID));
+    });
+}
+
+#[test]
+fn it_fails_set_cap_below_supply() {
+    new_test_ext().execute_with(|| {
+        let caller_acc = BALANCES[0].0;
+        let total_supply = get_test_total_supply();
+
+        let below_supply_result = PalletErc20::set_cap(Origin::root(), ASSET_ID, Some(total_supply - 1));
+        let bad_origin_result = PalletErc20::set_cap(Origin::signed(caller_acc), ASSET_ID, Some(total_supply));
+
+        assert_noop!(below_supply_result, RuntimeError::CapExceeded);
+        assert_noop!(bad_origin_result, DispatchError::BadOrigin);
+        assert_eq!(None, PalletErc20::get_cap(ASSET_ID));
+    });
+}
+
+#[test]
+fn it_works_snapshot_balances() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = BALANCES[0].0;
+        let reciever_acc = BALANCES[3].0;
+        let total_supply = get_test_total_supply();
+
+        let snapshot_result = PalletErc20::snapshot(Origin::root(), ASSET_ID);
+        let event = last_event().unwrap();
+        let _ = PalletErc20::transfer(Origin::signed(sender_acc), ASSET_ID, reciever_acc, 500);
+        let _ = PalletErc20::mint(Origin::root(), ASSET_ID, reciever_acc, 100);
+
+        assert_ok!(snapshot_result, ().into());
+        assert_eq!(Event::pallet_erc20(crate::Event::Snapshot { asset_id: ASSET_ID, snapshot_id: 1 }), event);
+        assert_eq!(1, PalletErc20::get_current_snapshot_id(ASSET_ID));
+        assert_eq!(Some(BALANCES[0].1), PalletErc20::balance_of_at(ASSET_ID, &sender_acc, 1));
+        assert_eq!(Some(BALANCES[3].1), PalletErc20::balance_of_at(ASSET_ID, &reciever_acc, 1));
+        assert_eq!(Some(total_supply), PalletErc20::total_supply_at(ASSET_ID, 1));
+
+        let _ = PalletErc20::snapshot(Origin::root(), ASSET_ID);
+        let _ = PalletErc20::burn(Origin::root(), ASSET_ID, reciever_acc, 200);
+        let _ = PalletErc20::snapshot(Origin::root(), ASSET_ID);
+
+        assert_eq!(Some(BALANCES[3].1), PalletErc20::balance_of_at(ASSET_ID, &reciever_acc, 1));
+        assert_eq!(Some(BALANCES[3].1 + 600), PalletErc20::balance_of_at(ASSET_ID, &reciever_acc, 2));
+        assert_eq!(Some(BALANCES[3].1 + 400), PalletErc20::balance_of_at(ASSET_ID, &reciever_acc, 3));
+        assert_eq!(Some(total_supply + 100), PalletErc20::total_supply_at(ASSET_ID, 2));
+        assert_eq!(Some(total_supply - 100), PalletErc20::total_supply_at(ASSET_ID, 3));
+        // accounts untouched since the first snapshot keep their current balance
+        assert_eq!(Some(BALANCES[1].1), PalletErc20::balance_of_at(ASSET_ID, &BALANCES[1].0, 3));
+    });
+}
+
+#[test]
+fn it_fails_snapshot_unknown_id() {
+    new_test_ext().execute_with(|| {
+        let holder_acc = BALANCES[0].0;
+
+        let bad_origin_result = PalletErc20::snapshot(Origin::signed(holder_acc), ASSET_ID);
+        let _ = PalletErc20::snapshot(Origin::root(), ASSET_ID);
+
+        assert_noop!(bad_origin_result, DispatchError::BadOrigin);
+        assert_eq!(None, PalletErc20::balance_of_at(ASSET_ID, &holder_acc, 0));
+        assert_eq!(None, PalletErc20::balance_of_at(ASSET_ID, &holder_acc, 2));
+        assert_eq!(None, PalletErc20::total_supply_at(ASSET_ID, 2));
+        assert_eq!(None, SecondErc20::total_supply_at(ASSET_ID, 1));
+    });
+}
+
+#[test]
+fn it_works_delegate_votes() {
+    new_test_ext().execute_with(|| {
+        let holder_acc = BALANCES[0].0;
+        let other_acc = BALANCES[1].0;
+        let delegatee_acc = BALANCES[3].0;
+
+        let delegate_result = PalletErc20::delegate(Origin::signed(holder_acc), ASSET_ID, delegatee_acc);
+        let event = last_event().unwrap();
+
+        assert_ok!(delegate_result, ().into());
+        let check_event = Event::pallet_erc20(crate::Event::DelegateChanged {
+            asset_id: ASSET_ID,
+            delegator: holder_acc,
+            from_delegate: None,
+            to_delegate: delegatee_acc,
+        });
+        assert_eq!(check_event, event);
+        assert_eq!(Some(delegatee_acc), PalletErc20::get_delegate(ASSET_ID, holder_acc));
+        assert_eq!(BALANCES[0].1, PalletErc20::get_votes(ASSET_ID, &delegatee_acc));
+        assert_eq!(0, PalletErc20::get_votes(ASSET_ID, &holder_acc));
+
+        // the other holder delegates to itself
+        let _ = PalletErc20::delegate(Origin::signed(other_acc), ASSET_ID, other_acc);
+        let _ = PalletErc20::transfer(Origin::signed(holder_acc), ASSET_ID, other_acc, 500);
+        let _ = PalletErc20::mint(Origin::root(), ASSET_ID, holder_acc, 100);
+        let _ = PalletErc20::burn(Origin::root(), ASSET_ID, other_acc, 200);
+
+        assert_eq!(BALANCES[0].1 - 400, PalletErc20::get_votes(ASSET_ID, &delegatee_acc));
+        assert_eq!(BALANCES[1].1 + 300, PalletErc20::get_votes(ASSET_ID, &other_acc));
+
+        // redelegation moves the whole balance
+        let redelegate_result = PalletErc20::delegate(Origin::signed(holder_acc), ASSET_ID, other_acc);
+
+        assert_ok!(redelegate_result, ().into());
+        assert_eq!(0, PalletErc20::get_votes(ASSET_ID, &delegatee_acc));
+        assert_eq!(BALANCES[0].1 + BALANCES[1].1 - 100, PalletErc20::get_votes(ASSET_ID, &other_acc));
+    });
+}
+
+#[test]
+fn it_works_redelegate_reserved_votes() {
+    new_test_ext().execute_with(|| {
+        let holder_acc = BALANCES[0].0;
+        let first_delegatee_acc = 10;
+        let second_delegatee_acc = 11;
+        let _ = PalletErc20::delegate(Origin::signed(holder_acc), ASSET_ID, first_delegatee_acc);
+        let _ = PalletErc20::reserve(ASSET_ID, &holder_acc, 200_000);
+
+        assert_eq!(BALANCES[0].1, PalletErc20::get_votes(ASSET_ID, &first_delegatee_acc));
+
+        let redelegate_result = PalletErc20::delegate(Origin::signed(holder_acc), ASSET_ID, second_delegatee_acc);
+
+        assert_ok!(redelegate_result, ().into());
+        assert_eq!(0, PalletErc20::get_votes(ASSET_ID, &first_delegatee_acc));
+        assert_eq!(BALANCES[0].1, PalletErc20::get_votes(ASSET_ID, &second_delegatee_acc));
+    });
+}
+
+#[test]
+fn it_works_past_votes() {
+    new_test_ext().execute_with(|| {
+        let holder_acc = BALANCES[0].0;
+        let delegatee_acc = BALANCES[3].0;
+
+        System::set_block_number(2);
+        let _ = PalletErc20::delegate(Origin::signed(holder_acc), ASSET_ID, delegatee_acc);
+        let _ = PalletErc20::burn(Origin::root(), ASSET_ID, holder_acc, 100);
+        System::set_block_number(5);
+        let _ = PalletErc20::burn(Origin::root(), ASSET_ID, holder_acc, 400);
+        System::set_block_number(7);
+
+        assert_eq!(2, PalletErc20::get_num_checkpoints(ASSET_ID, delegatee_acc));
+        assert_eq!(Some((5, BALANCES[0].1 - 500)), PalletErc20::get_checkpoint(ASSET_ID, (delegatee_acc, 1)));
+        assert_eq!(Some(0), PalletErc20::get_past_votes(ASSET_ID, &delegatee_acc, 1));
+        assert_eq!(Some(BALANCES[0].1 - 100), PalletErc20::get_past_votes(ASSET_ID, &delegatee_acc, 2));
+        assert_eq!(Some(BALANCES[0].1 - 100), PalletErc20::get_past_votes(ASSET_ID, &delegatee_acc, 4));
+        assert_eq!(Some(BALANCES[0].1 - 500), PalletErc20::get_past_votes(ASSET_ID, &delegatee_acc, 6));
+        assert_eq!(None, PalletErc20::get_past_votes(ASSET_ID, &delegatee_acc, 7));
+    });
+}
+
+fn sign_delegation(signer: AccountId, delegator: AccountId, delegatee: AccountId, expiry: u64) -> TestSignature {
+    let nonce = PalletErc20::get_permit_nonce(delegator);
+    TestSignature(signer, PalletErc20::delegation_payload(ASSET_ID, &delegator, &delegatee, nonce, expiry))
+}
+
+#[test]
+fn it_works_delegate_by_sig() {
+    new_test_ext().execute_with(|| {
+        let holder_acc = BALANCES[0].0;
+        let delegatee_acc = BALANCES[2].0;
+        let relayer_acc = BALANCES[3].0;
+        let expiry = 10;
+
+        let signature = sign_delegation(holder_acc, holder_acc, delegatee_acc, expiry);
+        let delegate_result = PalletErc20::delegate_by_sig(Origin::signed(relayer_acc), ASSET_ID, holder_acc, delegatee_acc, expiry, signature.clone());
+        let replay_result = PalletErc20::delegate_by_sig(Origin::signed(relayer_acc), ASSET_ID, holder_acc, delegatee_acc, expiry, signature);
+
+        assert_ok!(delegate_result, ().into());
+        assert_noop!(replay_result, RuntimeError::BadDelegationSignature);
+        assert_eq!(Some(delegatee_acc), PalletErc20::get_delegate(ASSET_ID, holder_acc));
+        assert_eq!(BALANCES[0].1, PalletErc20::get_votes(ASSET_ID, &delegatee_acc));
+        assert_eq!(1, PalletErc20::get_permit_nonce(holder_acc));
+    });
+}
+
+#[test]
+fn it_fails_delegate_by_sig() {
+    new_test_ext().execute_with(|| {
+        let holder_acc = BALANCES[0].0;
+        let delegatee_acc = BALANCES[2].0;
+        let relayer_acc = BALANCES[3].0;
+        let expiry = 10;
+
+        let wrong_signer = sign_delegation(delegatee_acc, holder_acc, delegatee_acc, expiry);
+        let wrong_signer_result = PalletErc20::delegate_by_sig(Origin::signed(relayer_acc), ASSET_ID, holder_acc, delegatee_acc, expiry, wrong_signer);
+        let signature = sign_delegation(holder_acc, holder_acc, delegatee_acc, expiry);
+        System::set_block_number(expiry + 1);
+        let expired_result = PalletErc20::delegate_by_sig(Origin::signed(relayer_acc), ASSET_ID, holder_acc, delegatee_acc, expiry, signature);
+
+        assert_noop!(wrong_signer_result, RuntimeError::BadDelegationSignature);
+        assert_noop!(expired_result, RuntimeError::DelegationExpired);
+        assert_eq!(None, PalletErc20::get_delegate(ASSET_ID, holder_acc));
+        assert_eq!(0, PalletErc20::get_votes(ASSET_ID, &delegatee_acc));
+    });
+}
+
+#[test]
+fn it_works_transfer_batch() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = BALANCES[0].0;
+        let transfers = vec![(BALANCES[1].0, 100), (BALANCES[2].0, 200), (BALANCES[3].0, 300)];
+
+        let batch_result = PalletErc20::transfer_batch(Origin::signed(sender_acc), ASSET_ID, transfers.clone());
+        let transfer_events: Vec<Event> = System::events().into_iter().map(|record| record.event).collect();
+
+        assert_ok!(batch_result, ().into());
+        assert_eq!(BALANCES[0].1 - 600, PalletErc20::get_balance(ASSET_ID, sender_acc));
+        let mut sender_balance = BALANCES[0].1;
+        for (i, (to, amount)) in transfers.into_iter().enumerate() {
+            sender_balance -= amount;
+            let check_event = Event::pallet_erc20(crate::Event::Transfer {
+                asset_id: ASSET_ID,
+                from: sender_acc,
+                to,
+                amount,
+                from_balance: sender_balance,
+                to_balance: BALANCES[i + 1].1 + amount,
+            });
+            assert_eq!(BALANCES[i + 1].1 + amount, PalletErc20::get_balance(ASSET_ID, to));
+            assert_eq!(check_event, transfer_events[i]);
+        }
+    });
+}
+
+#[test]
+fn it_fails_transfer_batch_atomically() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = BALANCES[2].0;
+        let transfers = vec![(BALANCES[0].0, 100), (BALANCES[1].0, BALANCES[2].1)];
+        let too_large = vec![(BALANCES[0].0, 1); MaxBatchSize::get() as usize + 1];
+
+        let batch_result = PalletErc20::transfer_batch(Origin::signed(sender_acc), ASSET_ID, transfers);
+        let too_large_result = PalletErc20::transfer_batch(Origin::signed(sender_acc), ASSET_ID, too_large);
+
+        assert_noop!(batch_result, RuntimeError::TransferAmountExceedsBalance);
+        assert_noop!(too_large_result, RuntimeError::BatchTooLarge);
+        assert_eq!(BALANCES[2].1, PalletErc20::get_balance(ASSET_ID, sender_acc));
+        assert_eq!(BALANCES[0].1, PalletErc20::get_balance(ASSET_ID, BALANCES[0].0));
+    });
+}
+
+#[test]
+fn it_works_transfer_from_batch() {
+    new_test_ext().execute_with(|| {
+        let owner_acc = BALANCES[0].0;
+        let spender_acc = BALANCES[1].0;
+        let transfers = vec![(BALANCES[2].0, 100), (BALANCES[3].0, 300)];
+        let _ = PalletErc20::approve(Origin::signed(owner_acc), ASSET_ID, spender_acc, 1000);
+
+        let batch_result = PalletErc20::transfer_from_batch(Origin::signed(spender_acc), ASSET_ID, owner_acc, transfers);
+
+        assert_ok!(batch_result, ().into());
+        assert_eq!(600, PalletErc20::get_allowance((ASSET_ID, owner_acc), spender_acc));
+        assert_eq!(BALANCES[0].1 - 400, PalletErc20::get_balance(ASSET_ID, owner_acc));
+        assert_eq!(BALANCES[3].1 + 300, PalletErc20::get_balance(ASSET_ID, BALANCES[3].0));
+    });
+}
+
+#[test]
+fn it_fails_transfer_from_batch_insufficient_allowance() {
+    new_test_ext().execute_with(|| {
+        let owner_acc = BALANCES[0].0;
+        let spender_acc = BALANCES[1].0;
+        let transfers = vec![(BALANCES[2].0, 600), (BALANCES[3].0, 600)];
+        let _ = PalletErc20::approve(Origin::signed(owner_acc), ASSET_ID, spender_acc, 1000);
+
+        let batch_result = PalletErc20::transfer_from_batch(Origin::signed(spender_acc), ASSET_ID, owner_acc, transfers);
+
+        assert_noop!(batch_result, RuntimeError::InsufficientAllowance);
+        assert_eq!(1000, PalletErc20::get_allowance((ASSET_ID, owner_acc), spender_acc));
+        assert_eq!(BALANCES[0].1, PalletErc20::get_balance(ASSET_ID, owner_acc));
+        assert_eq!(BALANCES[2].1, PalletErc20::get_balance(ASSET_ID, BALANCES[2].0));
+    });
+}
+
+#[test]
+fn it_works_genesis_vesting_cliff_and_release() {
+    new_test_ext().execute_with(|| {
+        let (vesting_acc, starting_block, cliff, per_block, locked) = SECOND_VESTING;
+        let reciever_acc = SECOND_BALANCES[0].0;
+        let unlocked = SECOND_BALANCES[1].1 - locked;
+
+        let over_unlocked_result = SecondErc20::transfer(Origin::signed(vesting_acc), ASSET_ID, reciever_acc, unlocked + 1);
+        let unlocked_result = SecondErc20::transfer(Origin::signed(vesting_acc), ASSET_ID, reciever_acc, unlocked);
+
+        assert_eq!(
+            Some(VestingInfo { locked, per_block, starting_block, cliff }),
+            SecondErc20::get_vesting(ASSET_ID, vesting_acc)
+        );
+        assert_noop!(over_unlocked_result, Error::<Test, Instance1>::LockedByVesting);
+        assert_ok!(unlocked_result, ().into());
+
+        // nothing is released until the cliff ends
+        System::set_block_number(starting_block + cliff - 1);
+        let before_cliff_result = SecondErc20::transfer(Origin::signed(vesting_acc), ASSET_ID, reciever_acc, 1);
+
+        assert_eq!(locked, SecondErc20::vesting_locked(ASSET_ID, &vesting_acc));
+        assert_noop!(before_cliff_result, Error::<Test, Instance1>::LockedByVesting);
+
+        // at the cliff end the blocks since the start are released at once
+        System::set_block_number(starting_block + cliff);
+        let released = cliff * per_block;
+        let over_released_result = SecondErc20::transfer(Origin::signed(vesting_acc), ASSET_ID, reciever_acc, released + 1);
+        let released_result = SecondErc20::transfer(Origin::signed(vesting_acc), ASSET_ID, reciever_acc, released);
+
+        assert_noop!(over_released_result, Error::<Test, Instance1>::LockedByVesting);
+        assert_ok!(released_result, ().into());
+        assert_eq!(locked - released, SecondErc20::get_balance(ASSET_ID, vesting_acc));
+
+        // then one block releases per_block
+        System::set_block_number(starting_block + cliff + 1);
+        let linear_result = SecondErc20::transfer(Origin::signed(vesting_acc), ASSET_ID, reciever_acc, per_block);
+
+        assert_ok!(linear_result, ().into());
+        assert_eq!(locked - released - per_block, SecondErc20::vesting_locked(ASSET_ID, &vesting_acc));
+    });
+}
+
+#[test]
+fn it_works_vest_completes_schedule() {
+    new_test_ext().execute_with(|| {
+        let (vesting_acc, starting_block, _, per_block, locked) = SECOND_VESTING;
+
+        let vest_result = SecondErc20::vest(Origin::signed(vesting_acc), ASSET_ID);
+        let event = last_event().unwrap();
+
+        assert_ok!(vest_result, ().into());
+        assert_eq!(Event::pallet_erc20_Instance1(crate::Event::<Test, Instance1>::VestingUpdated { asset_id: ASSET_ID, who: vesting_acc, still_locked: locked }), event);
+
+        System::set_block_number(starting_block + locked / per_block);
+        let vest_result = SecondErc20::vest(Origin::signed(vesting_acc), ASSET_ID);
+        let event = last_event().unwrap();
+        let not_vesting_result = SecondErc20::vest(Origin::signed(vesting_acc), ASSET_ID);
+
+        assert_ok!(vest_result, ().into());
+        assert_eq!(Event::pallet_erc20_Instance1(crate::Event::<Test, Instance1>::VestingCompleted { asset_id: ASSET_ID, who: vesting_acc }), event);
+        assert_noop!(not_vesting_result, Error::<Test, Instance1>::NotVesting);
+        assert_eq!(None, SecondErc20::get_vesting(ASSET_ID, vesting_acc));
+        assert_eq!(0, SecondErc20::vesting_locked(ASSET_ID, &vesting_acc));
+    });
+}
+
+#[test]
+fn it_works_vested_transfer() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = BALANCES[0].0;
+        let target_acc = BALANCES[3].0;
+        let schedule = VestingInfo { locked: 1000, per_block: 10, starting_block: 1, cliff: 0 };
+
+        let vested_result = PalletErc20::vested_transfer(Origin::signed(sender_acc), ASSET_ID, target_acc, schedule);
+        let event = last_event().unwrap();
+        let locked_transfer_result = PalletErc20::transfer(Origin::signed(target_acc), ASSET_ID, sender_acc, 1);
+
+        assert_ok!(vested_result, ().into());
+        assert_eq!(Event::pallet_erc20(crate::Event::VestingCreated { asset_id: ASSET_ID, who: target_acc, locked: 1000 }), event);
+        assert_eq!(1000, PalletErc20::get_balance(ASSET_ID, target_acc));
+        assert_noop!(locked_transfer_result, RuntimeError::LockedByVesting);
+
+        System::set_block_number(51);
+        let half_result = PalletErc20::transfer(Origin::signed(target_acc), ASSET_ID, sender_acc, 500);
+        let over_half_result = PalletErc20::transfer(Origin::signed(target_acc), ASSET_ID, sender_acc, 1);
+
+        assert_ok!(half_result, ().into());
+        assert_noop!(over_half_result, RuntimeError::LockedByVesting);
+    });
+}
+
+#[test]
+fn it_fails_vested_transfer() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = BALANCES[0].0;
+        let target_acc = BALANCES[3].0;
+        let schedule = VestingInfo { locked: 1000, per_block: 10, starting_block: 1, cliff: 0 };
+        let invalid_schedule = VestingInfo { per_block: 0, ..schedule };
+
+        let invalid_result = PalletErc20::vested_transfer(Origin::signed(sender_acc), ASSET_ID, target_acc, invalid_schedule);
+        let _ = PalletErc20::vested_transfer(Origin::signed(sender_acc), ASSET_ID, target_acc, schedule);
+        let existing_result = PalletErc20::vested_transfer(Origin::signed(sender_acc), ASSET_ID, target_acc, schedule);
+        let poor_sender_result = PalletErc20::vested_transfer(Origin::signed(BALANCES[2].0), ASSET_ID, BALANCES[1].0, VestingInfo { locked: BALANCES[2].1 + 1, ..schedule });
+
+        assert_noop!(invalid_result, RuntimeError::InvalidVestingSchedule);
+        assert_noop!(existing_result, RuntimeError::ExistingVestingSchedule);
+        assert_noop!(poor_sender_result, RuntimeError::TransferAmountExceedsBalance);
+        assert_eq!(None, PalletErc20::get_vesting(ASSET_ID, BALANCES[1].0));
+        assert_eq!(BALANCES[0].1 - 1000, PalletErc20::get_balance(ASSET_ID, sender_acc));
+    });
+}
+
+#[test]
+fn it_works_reserve_and_unreserve() {
+    new_test_ext().execute_with(|| {
+        let holder_acc = BALANCES[0].0;
+        let reciever_acc = BALANCES[3].0;
+        let total_supply = get_test_total_supply();
+
+        let reserve_result = PalletErc20::reserve(ASSET_ID, &holder_acc, 1000);
+        let event = last_event().unwrap();
+        let over_free_result = PalletErc20::transfer(Origin::signed(holder_acc), ASSET_ID, reciever_acc, BALANCES[0].1 - 999);
+
+        assert_ok!(reserve_result);
+        assert_eq!(Event::pallet_erc20(crate::Event::Reserved { asset_id: ASSET_ID, who: holder_acc, amount: 1000 }), event);
+        assert_eq!(BALANCES[0].1 - 1000, PalletErc20::get_balance(ASSET_ID, holder_acc));
+        assert_eq!(1000, PalletErc20::get_reserved_balance(ASSET_ID, holder_acc));
+        assert_eq!(BALANCES[0].1, <GenesisToken as Currency<_>>::total_balance(&holder_acc));
+        assert_eq!(total_supply, PalletErc20::get_total_supply(ASSET_ID));
+        assert_noop!(over_free_result, RuntimeError::TransferAmountExceedsBalance);
+
+        let not_reserved = PalletErc20::unreserve(ASSET_ID, &holder_acc, 1500);
+
+        assert_eq!(500, not_reserved);
+        assert_eq!(BALANCES[0].1, PalletErc20::get_balance(ASSET_ID, holder_acc));
+        assert_eq!(0, PalletErc20::get_reserved_balance(ASSET_ID, holder_acc));
+    });
+}
+
+#[test]
+fn it_fails_reserve_above_free_balance() {
+    new_test_ext().execute_with(|| {
+        let holder_acc = BALANCES[2].0;
+
+        let reserve_result = PalletErc20::reserve(ASSET_ID, &holder_acc, BALANCES[2].1 + 1);
+
+        assert_noop!(reserve_result, RuntimeError::InsufficientBalance);
+        assert!(!<GenesisToken as ReservableCurrency<_>>::can_reserve(&holder_acc, BALANCES[2].1 + 1));
+        assert!(<GenesisToken as ReservableCurrency<_>>::can_reserve(&holder_acc, BALANCES[2].1));
+    });
+}
+
+#[test]
+fn it_works_repatriate_reserved() {
+    new_test_ext().execute_with(|| {
+        let slashed_acc = BALANCES[0].0;
+        let beneficiary_acc = BALANCES[3].0;
+        let _ = PalletErc20::reserve(ASSET_ID, &slashed_acc, 1000);
+
+        let to_free_result = PalletErc20::repatriate_reserved(ASSET_ID, &slashed_acc, &beneficiary_acc, 400, BalanceStatus::Free);
+        let event = last_event().unwrap();
+        let to_reserved_result = PalletErc20::repatriate_reserved(ASSET_ID, &slashed_acc, &beneficiary_acc, 800, BalanceStatus::Reserved);
+
+        assert_eq!(Ok(0), to_free_result);
+        assert_eq!(
+            Event::pallet_erc20(crate::Event::ReserveRepatriated {
+                asset_id: ASSET_ID,
+                from: slashed_acc,
+                to: beneficiary_acc,
+                amount: 400,
+                status: BalanceStatus::Free,
+            }),
+            event
+        );
+        assert_eq!(Ok(200), to_reserved_result);
+        assert_eq!(0, PalletErc20::get_reserved_balance(ASSET_ID, slashed_acc));
+        assert_eq!(BALANCES[3].1 + 400, PalletErc20::get_balance(ASSET_ID, beneficiary_acc));
+        assert_eq!(600, PalletErc20::get_reserved_balance(ASSET_ID, beneficiary_acc));
+        assert_eq!(get_test_total_supply(), PalletErc20::get_total_supply(ASSET_ID));
+    });
+}
+
+#[test]
+fn it_works_locks_restrict_transfers() {
+    new_test_ext().execute_with(|| {
+        let holder_acc = BALANCES[0].0;
+        let reciever_acc = BALANCES[3].0;
+        let unlocked = BALANCES[0].1 - 2000;
+
+        <GenesisToken as LockableCurrency<_>>::set_lock(*b"staking ", &holder_acc, 1000, WithdrawReasons::all());
+        <GenesisToken as LockableCurrency<_>>::set_lock(*b"democrac", &holder_acc, 2000, WithdrawReasons::TRANSFER);
+        let locked_result = PalletErc20::transfer(Origin::signed(holder_acc), ASSET_ID, reciever_acc, unlocked + 1);
+        let unlocked_result = PalletErc20::transfer(Origin::signed(holder_acc), ASSET_ID, reciever_acc, unlocked);
+
+        assert_eq!(2, PalletErc20::get_locks(ASSET_ID, holder_acc).len());
+        assert_noop!(locked_result, RuntimeError::LiquidityRestrictions);
+        assert_ok!(unlocked_result, ().into());
+
+        // the reserve reason is locked only by the smaller lock
+        let reserve_result = PalletErc20::reserve(ASSET_ID, &holder_acc, 1000);
+        <GenesisToken as LockableCurrency<_>>::remove_lock(*b"democrac", &holder_acc);
+        let released_result = PalletErc20::transfer(Origin::signed(holder_acc), ASSET_ID, reciever_acc, 1);
+
+        assert_ok!(reserve_result);
+        assert_noop!(released_result, RuntimeError::LiquidityRestrictions);
+
+        <GenesisToken as LockableCurrency<_>>::remove_lock(*b"staking ", &holder_acc);
+        let released_result = PalletErc20::transfer(Origin::signed(holder_acc), ASSET_ID, reciever_acc, 1000);
+
+        assert_ok!(released_result, ().into());
+        assert!(PalletErc20::get_locks(ASSET_ID, holder_acc).is_empty());
+    });
+}
+
+#[test]
+fn it_works_currency_imbalances() {
+    new_test_ext().execute_with(|| {
+        let holder_acc = BALANCES[0].0;
+        let total_supply = get_test_total_supply();
+
+        let deposit = <GenesisToken as Currency<_>>::deposit_creating(&holder_acc, 500);
+        assert_eq!(total_supply, PalletErc20::get_total_supply(ASSET_ID));
+        drop(deposit);
+        assert_eq!(total_supply + 500, PalletErc20::get_total_supply(ASSET_ID));
+        assert_eq!(BALANCES[0].1 + 500, PalletErc20::get_balance(ASSET_ID, holder_acc));
+
+        let withdrawn = <GenesisToken as Currency<_>>::withdraw(
+            &holder_acc, 300, WithdrawReasons::TRANSFER, ExistenceRequirement::AllowDeath
+        );
+        assert!(withdrawn.is_ok());
+        drop(withdrawn);
+        assert_eq!(total_supply + 200, PalletErc20::get_total_supply(ASSET_ID));
+
+        let _ = PalletErc20::reserve(ASSET_ID, &holder_acc, 100);
+        let (slashed, remaining) = <GenesisToken as ReservableCurrency<_>>::slash_reserved(&holder_acc, 150);
+        assert_eq!(50, remaining);
+        drop(slashed);
+        assert_eq!(total_supply + 100, PalletErc20::get_total_supply(ASSET_ID));
+        assert_eq!(0, PalletErc20::get_reserved_balance(ASSET_ID, holder_acc));
+    });
+}
+
+#[test]
+fn it_fails_currency_deposit_above_cap() {
+    new_test_ext().execute_with(|| {
+        let holder_acc = 7;
+        let total_supply = get_second_total_supply();
+
+        let deposit = <SecondGenesisToken as Currency<_>>::deposit_creating(&holder_acc, 100_000);
+        let resized = <SecondGenesisToken as Currency<_>>::make_free_balance_be(&holder_acc, 100_000);
+
+        assert_eq!(0, deposit.peek());
+        assert!(matches!(resized, SignedImbalance::Positive(ref imbalance) if imbalance.peek() == 0));
+        assert_eq!(0, SecondErc20::get_balance(ASSET_ID, holder_acc));
+
+        let issued = <SecondGenesisToken as Currency<_>>::issue(100_000);
+
+        assert_eq!(SECOND_CAP - total_supply, issued.peek());
+        assert_eq!(SECOND_CAP, SecondErc20::get_total_supply(ASSET_ID));
+        drop(issued);
+        assert_eq!(total_supply, SecondErc20::get_total_supply(ASSET_ID));
+    });
+}
+
+#[test]
+fn it_fails_currency_deposit_checks() {
+    new_test_ext().execute_with(|| {
+        let holder_acc = BALANCES[0].0;
+        let empty_acc = BALANCES[3].0;
+        let frozen_acc = BALANCES[1].0;
+        let sentinel_acc = 0;
+        let total_supply = get_test_total_supply();
+        let _ = PalletErc20::freeze_account(Origin::root(), ASSET_ID, frozen_acc);
+        set_minimum_balance(100);
+
+        let dead_result = <GenesisToken as Currency<_>>::deposit_into_existing(&empty_acc, 500);
+        let frozen_result = <GenesisToken as Currency<_>>::deposit_into_existing(&frozen_acc, 500);
+        let sentinel_deposit = <GenesisToken as Currency<_>>::deposit_creating(&sentinel_acc, 500);
+        let dust_deposit = <GenesisToken as Currency<_>>::deposit_creating(&empty_acc, 50);
+
+        assert_eq!(Some(RuntimeError::DeadAccount.into()), dead_result.err());
+        assert_eq!(Some(RuntimeError::AccountFrozen.into()), frozen_result.err());
+        assert_eq!(0, sentinel_deposit.peek());
+        assert_eq!(0, dust_deposit.peek());
+        assert_eq!(0, PalletErc20::get_balance(ASSET_ID, empty_acc));
+
+        let _ = PalletErc20::pause(Origin::root(), ASSET_ID);
+        let paused_result = <GenesisToken as Currency<_>>::deposit_into_existing(&holder_acc, 500);
+        let paused_issue = <GenesisToken as Currency<_>>::issue(500);
+
+        assert_eq!(Some(RuntimeError::TokenPaused.into()), paused_result.err());
+        assert_eq!(0, paused_issue.peek());
+        assert_eq!(total_supply, PalletErc20::get_total_supply(ASSET_ID));
+    });
+}
+
+#[test]
+fn it_works_transfer_removes_dust() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = BALANCES[2].0;
+        let reciever_acc = BALANCES[0].0;
+        let total_supply = get_test_total_supply();
+        set_minimum_balance(100);
+
+        let transfer_result = PalletErc20::transfer(Origin::signed(sender_acc), ASSET_ID, reciever_acc, BALANCES[2].1 - 50);
+        let dust_event = System::events().into_iter()
+            .map(|record| record.event)
+            .find(|event| matches!(event, Event::pallet_erc20(crate::Event::DustLost { .. })));
+
+        assert_ok!(transfer_result, ().into());
+        assert_eq!(Some(Event::pallet_erc20(crate::Event::DustLost { asset_id: ASSET_ID, who: sender_acc, amount: 50 })), dust_event);
+        assert_eq!(0, PalletErc20::get_balance(ASSET_ID, sender_acc));
+        assert!(!crate::BalanceOf::<Test>::contains_key(ASSET_ID, sender_acc));
+        assert_eq!(50, PalletErc20::get_balance(ASSET_ID, DUST_TRAP_ACC));
+        assert_eq!(total_supply, PalletErc20::get_total_supply(ASSET_ID));
+    });
+}
+
+#[test]
+fn it_fails_transfer_keep_alive_leaving_dust() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = BALANCES[2].0;
+        let reciever_acc = BALANCES[0].0;
+        set_minimum_balance(100);
+
+        let keep_alive_result = <GenesisToken as Currency<_>>::transfer(
+            &sender_acc, &reciever_acc, BALANCES[2].1 - 50, ExistenceRequirement::KeepAlive
+        );
+        let above_minimum_result = <GenesisToken as Currency<_>>::transfer(
+            &sender_acc, &reciever_acc, BALANCES[2].1 - 100, ExistenceRequirement::KeepAlive
+        );
+
+        assert_noop!(keep_alive_result, RuntimeError::KeepAlive);
+        assert_ok!(above_minimum_result);
+        assert_eq!(100, PalletErc20::get_balance(ASSET_ID, sender_acc));
+        assert_eq!(0, PalletErc20::reducible_balance(ASSET_ID, &sender_acc, true));
+        assert_eq!(100, PalletErc20::reducible_balance(ASSET_ID, &sender_acc, false));
+    });
+}
+
+#[test]
+fn it_fails_transfer_and_mint_below_minimum_balance() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = BALANCES[0].0;
+        let empty_acc = BALANCES[3].0;
+        set_minimum_balance(100);
+
+        let transfer_result = PalletErc20::transfer(Origin::signed(sender_acc), ASSET_ID, empty_acc, 99);
+        let mint_result = PalletErc20::mint(Origin::root(), ASSET_ID, empty_acc, 99);
+
+        assert_noop!(transfer_result, RuntimeError::ExistentialDeposit);
+        assert_noop!(mint_result, RuntimeError::ExistentialDeposit);
+        assert_eq!(100, <GenesisToken as Currency<_>>::minimum_balance());
+        assert_ok!(PalletErc20::transfer(Origin::signed(sender_acc), ASSET_ID, empty_acc, 100), ().into());
+    });
+}
+
+#[test]
+fn it_fails_create_token_below_minimum_balance() {
+    new_test_ext().execute_with(|| {
+        let owner_acc = BALANCES[2].0;
+        let asset_id = PalletErc20::get_next_asset_id();
+        set_minimum_balance(100);
+
+        let create_result = PalletErc20::create_token(Origin::root(), owner_acc, b"T".to_vec(), b"T".to_vec(), 18, 5);
+
+        assert_eq!(Some(RuntimeError::ExistentialDeposit.into()), create_result.err().map(|e| e.error));
+        assert_eq!(asset_id, PalletErc20::get_next_asset_id());
+        assert_eq!(None, PalletErc20::get_owner(asset_id));
+        assert!(PalletErc20::get_name(asset_id).is_empty());
+        assert!(System::events().into_iter().all(|record| !matches!(
+            record.event, Event::pallet_erc20(crate::Event::TokenCreated { .. })
+        )));
+    });
+}
+
+#[test]
+fn it_works_zero_balance_removes_storage_entry() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = BALANCES[2].0;
+        let reciever_acc = BALANCES[0].0;
+
+        let transfer_result = PalletErc20::transfer(Origin::signed(sender_acc), ASSET_ID, reciever_acc, BALANCES[2].1);
+
+        assert_ok!(transfer_result, ().into());
+        assert!(!crate::BalanceOf::<Test>::contains_key(ASSET_ID, sender_acc));
+        assert!(!crate::BalanceOf::<Test>::contains_key(ASSET_ID, BALANCES[3].0));
+        assert_eq!(0, PalletErc20::get_balance(ASSET_ID, sender_acc));
+    });
+}
+
+#[test]
+fn it_works_transfer_all() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = BALANCES[2].0;
+        let reciever_acc = BALANCES[3].0;
+
+        let transfer_result = PalletErc20::transfer_all(Origin::signed(sender_acc), ASSET_ID, reciever_acc);
+
+        assert_ok!(transfer_result, ().into());
+        assert_eq!(0, PalletErc20::get_balance(ASSET_ID, sender_acc));
+        assert_eq!(BALANCES[2].1, PalletErc20::get_balance(ASSET_ID, reciever_acc));
+        let check_event = Event::pallet_erc20(crate::Event::Transfer {
+            asset_id: ASSET_ID,
+            from: sender_acc,
+            to: reciever_acc,
+            amount: BALANCES[2].1,
+            from_balance: 0,
+            to_balance: BALANCES[2].1,
+        });
+        assert_eq!(Ok(check_event), last_event());
+    });
+}
+
+#[test]
+fn it_works_transfer_all_keeps_locked_tokens() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = BALANCES[2].0;
+        let reciever_acc = BALANCES[3].0;
+
+        <GenesisToken as LockableCurrency<_>>::set_lock(*b"staking ", &sender_acc, 400, WithdrawReasons::all());
+        let transfer_result = PalletErc20::transfer_all(Origin::signed(sender_acc), ASSET_ID, reciever_acc);
+
+        assert_ok!(transfer_result, ().into());
+        assert_eq!(400, PalletErc20::get_balance(ASSET_ID, sender_acc));
+        assert_eq!(BALANCES[2].1 - 400, PalletErc20::get_balance(ASSET_ID, reciever_acc));
+    });
+}
+
+#[test]
+fn it_works_transfer_keep_alive() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = BALANCES[2].0;
+        let reciever_acc = BALANCES[3].0;
+        set_minimum_balance(100);
+
+        let transfer_result = PalletErc20::transfer_keep_alive(Origin::signed(sender_acc), ASSET_ID, reciever_acc, BALANCES[2].1 - 100);
+
+        assert_ok!(transfer_result, ().into());
+        assert_eq!(100, PalletErc20::get_balance(ASSET_ID, sender_acc));
+        assert_eq!(BALANCES[2].1 - 100, PalletErc20::get_balance(ASSET_ID, reciever_acc));
+        let check_event = Event::pallet_erc20(crate::Event::Transfer {
+            asset_id: ASSET_ID,
+            from: sender_acc,
+            to: reciever_acc,
+            amount: BALANCES[2].1 - 100,
+            from_balance: 100,
+            to_balance: BALANCES[2].1 - 100,
+        });
+        assert_eq!(Ok(check_event), last_event());
+    });
+}
+
+#[test]
+fn it_fails_transfer_keep_alive_below_minimum_balance() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = BALANCES[2].0;
+        let reciever_acc = BALANCES[0].0;
+        set_minimum_balance(100);
+
+        let dust_result = PalletErc20::transfer_keep_alive(Origin::signed(sender_acc), ASSET_ID, reciever_acc, BALANCES[2].1 - 99);
+        let empty_result = PalletErc20::transfer_keep_alive(Origin::signed(sender_acc), ASSET_ID, reciever_acc, BALANCES[2].1);
+
+        assert_noop!(dust_result, RuntimeError::KeepAlive);
+        assert_noop!(empty_result, RuntimeError::KeepAlive);
+        assert_eq!(BALANCES[2].1, PalletErc20::get_balance(ASSET_ID, sender_acc));
+    });
+}
+
+#[test]
+fn it_works_approve_with_expiry() {
+    new_test_ext().execute_with(|| {
+        let owner_acc = BALANCES[0].0;
+        let spender_acc = BALANCES[1].0;
+        let reciever_acc = BALANCES[3].0;
+        let expires_at = 10;
+
+        let approve_result = PalletErc20::approve_with_expiry(Origin::signed(owner_acc), ASSET_ID, spender_acc, 1000, expires_at);
+
+        assert_ok!(approve_result, ().into());
+        let check_event = Event::pallet_erc20(crate::Event::ApprovalWithExpiry {
+            asset_id: ASSET_ID,
+            owner: owner_acc,
+            spender: spender_acc,
+            amount: 1000,
+            expires_at,
+        });
+        assert_eq!(Ok(check_event), last_event());
+        assert_eq!(Some(expires_at), PalletErc20::get_allowance_expiry((ASSET_ID, owner_acc), spender_acc));
+
+        System::set_block_number(expires_at);
+        let transfer_result = PalletErc20::transfer_from(Origin::signed(spender_acc), ASSET_ID, owner_acc, reciever_acc, 400);
+
+        assert_ok!(transfer_result, ().into());
+        assert_eq!(600, PalletErc20::get_allowance((ASSET_ID, owner_acc), spender_acc));
+        assert_eq!(Some(expires_at), PalletErc20::get_allowance_expiry((ASSET_ID, owner_acc), spender_acc));
+    });
+}
+
+#[test]
+fn it_fails_transfer_from_expired_allowance() {
+    new_test_ext().execute_with(|| {
+        let owner_acc = BALANCES[0].0;
+        let spender_acc = BALANCES[1].0;
+        let reciever_acc = BALANCES[3].0;
+        let expires_at = 10;
+
+        let _ = PalletErc20::approve_with_expiry(Origin::signed(owner_acc), ASSET_ID, spender_acc, u64::MAX, expires_at);
+        System::set_block_number(expires_at + 1);
+        let transfer_result = PalletErc20::transfer_from(Origin::signed(spender_acc), ASSET_ID, owner_acc, reciever_acc, 400);
+        let approve_result = PalletErc20::approve_with_expiry(Origin::signed(owner_acc), ASSET_ID, spender_acc, 1000, expires_at);
+
+        assert_noop!(transfer_result, RuntimeError::AllowanceExpired);
+        assert_noop!(approve_result, RuntimeError::AllowanceExpired);
+        assert_eq!(BALANCES[0].1, PalletErc20::get_balance(ASSET_ID, owner_acc));
+    });
+}
+
+#[test]
+fn it_works_approve_clears_expiry() {
+    new_test_ext().execute_with(|| {
+        let owner_acc = BALANCES[0].0;
+        let spender_acc = BALANCES[1].0;
+        let reciever_acc = BALANCES[3].0;
+
+        let _ = PalletErc20::approve_with_expiry(Origin::signed(owner_acc), ASSET_ID, spender_acc, 1000, 10);
+        let approve_result = PalletErc20::approve(Origin::signed(owner_acc), ASSET_ID, spender_acc, 1000);
+        System::set_block_number(11);
+        let transfer_result = PalletErc20::transfer_from(Origin::signed(spender_acc), ASSET_ID, owner_acc, reciever_acc, 1000);
+
+        assert_ok!(approve_result, ().into());
+        assert_ok!(transfer_result, ().into());
+        assert_eq!(None, PalletErc20::get_allowance_expiry((ASSET_ID, owner_acc), spender_acc));
+    });
+}
+
+#[test]
+fn it_works_revoke_allowance() {
+    new_test_ext().execute_with(|| {
+        let owner_acc = BALANCES[0].0;
+        let spender_acc = BALANCES[1].0;
+
+        let _ = PalletErc20::approve_with_expiry(Origin::signed(owner_acc), ASSET_ID, spender_acc, 1000, 10);
+        let revoke_result = PalletErc20::revoke_allowance(Origin::signed(owner_acc), ASSET_ID, spender_acc);
+
+        assert_ok!(revoke_result, ().into());
+        assert_eq!(Ok(Event::pallet_erc20(crate::Event::Approval { asset_id: ASSET_ID, owner: owner_acc, spender: spender_acc, amount: 0 })), last_event());
+        assert!(!crate::AllowanceOf::<Test>::contains_key((ASSET_ID, owner_acc), spender_acc));
+        assert_eq!(None, PalletErc20::get_allowance_expiry((ASSET_ID, owner_acc), spender_acc));
+    });
+}
+
+#[test]
+fn it_works_revoke_all_allowances() {
+    new_test_ext().execute_with(|| {
+        let owner_acc = BALANCES[0].0;
+        let other_acc = BALANCES[1].0;
+        let spenders = [2, 3, 4];
+
+        for spender_acc in spenders.iter() {
+            let _ = PalletErc20::approve(Origin::signed(owner_acc), ASSET_ID, *spender_acc, 1000);
+        }
+        let _ = PalletErc20::approve(Origin::signed(other_acc), ASSET_ID, 3, 500);
+
+        let mut allowances = PalletErc20::allowances_of(ASSET_ID, &owner_acc);
+        allowances.sort();
+        assert_eq!(vec![(2, 1000), (3, 1000), (4, 1000)], allowances);
+
+        // MaxSpenders allowances are removed per call
+        let first_result = PalletErc20::revoke_all_allowances(Origin::signed(owner_acc), ASSET_ID);
+        assert!(first_result.is_ok());
+        assert_eq!(1, PalletErc20::allowances_of(ASSET_ID, &owner_acc).len());
+
+        let second_result = PalletErc20::revoke_all_allowances(Origin::signed(owner_acc), ASSET_ID);
+        assert!(second_result.is_ok());
+        assert!(PalletErc20::allowances_of(ASSET_ID, &owner_acc).is_empty());
+        assert_eq!(vec![(3, 500)], PalletErc20::allowances_of(ASSET_ID, &other_acc));
+    });
+}
+
+#[test]
+fn it_fails_transfer_from_revoked_allowance() {
+    new_test_ext().execute_with(|| {
+        let owner_acc = BALANCES[0].0;
+        let spender_acc = BALANCES[1].0;
+        let reciever_acc = BALANCES[3].0;
+
+        let _ = PalletErc20::approve(Origin::signed(owner_acc), ASSET_ID, spender_acc, 1000);
+        let _ = PalletErc20::revoke_all_allowances(Origin::signed(owner_acc), ASSET_ID);
+        let transfer_result = PalletErc20::transfer_from(Origin::signed(spender_acc), ASSET_ID, owner_acc, reciever_acc, 1);
+
+        assert_noop!(transfer_result, RuntimeError::InsufficientAllowance);
+    });
+}
+
+#[test]
+fn it_works_approve_race_double_spend() {
+    new_test_ext().execute_with(|| {
+        let owner_acc = BALANCES[0].0;
+        let spender_acc = BALANCES[1].0;
+        let reciever_acc = BALANCES[3].0;
+
+        let _ = PalletErc20::approve(Origin::signed(owner_acc), ASSET_ID, spender_acc, 1000);
+        // spender front-runs the owner lowering the allowance to 500
+        let front_run_result = PalletErc20::transfer_from(Origin::signed(spender_acc), ASSET_ID, owner_acc, reciever_acc, 1000);
+        let approve_result = PalletErc20::approve(Origin::signed(owner_acc), ASSET_ID, spender_acc, 500);
+        let second_spend_result = PalletErc20::transfer_from(Origin::signed(spender_acc), ASSET_ID, owner_acc, reciever_acc, 500);
+
+        assert_ok!(front_run_result, ().into());
+        assert_ok!(approve_result, ().into());
+        assert_ok!(second_spend_result, ().into());
+        assert_eq!(1500, PalletErc20::get_balance(ASSET_ID, reciever_acc));
+    });
+}
+
+#[test]
+fn it_fails_approve_checked_after_front_run() {
+    new_test_ext().execute_with(|| {
+        let owner_acc = BALANCES[0].0;
+        let spender_acc = BALANCES[1].0;
+        let reciever_acc = BALANCES[3].0;
+
+        let _ = PalletErc20::approve(Origin::signed(owner_acc), ASSET_ID, spender_acc, 1000);
+        let front_run_result = PalletErc20::transfer_from(Origin::signed(spender_acc), ASSET_ID, owner_acc, reciever_acc, 1000);
+        let approve_result = PalletErc20::approve_checked(Origin::signed(owner_acc), ASSET_ID, spender_acc, 1000, 500);
+
+        assert_ok!(front_run_result, ().into());
+        assert_noop!(approve_result, RuntimeError::AllowanceMismatch);
+        assert_eq!(0, PalletErc20::get_allowance((ASSET_ID, owner_acc), spender_acc));
+        assert_eq!(1000, PalletErc20::get_balance(ASSET_ID, reciever_acc));
+    });
+}
+
+#[test]
+fn it_works_approve_checked() {
+    new_test_ext().execute_with(|| {
+        let owner_acc = BALANCES[0].0;
+        let spender_acc = BALANCES[1].0;
+
+        let _ = PalletErc20::approve(Origin::signed(owner_acc), ASSET_ID, spender_acc, 1000);
+        let approve_result = PalletErc20::approve_checked(Origin::signed(owner_acc), ASSET_ID, spender_acc, 1000, 500);
+
+        assert_ok!(approve_result, ().into());
+        assert_eq!(Ok(Event::pallet_erc20(crate::Event::Approval { asset_id: ASSET_ID, owner: owner_acc, spender: spender_acc, amount: 500 })), last_event());
+        assert_eq!(500, PalletErc20::get_allowance((ASSET_ID, owner_acc), spender_acc));
+    });
+}
+
+#[test]
+fn it_fails_strict_approve_non_zero_change() {
+    new_test_ext().execute_with(|| {
+        let owner_acc = SECOND_BALANCES[0].0;
+        let spender_acc = BALANCES[1].0;
+
+        let first_result = SecondErc20::approve(Origin::signed(owner_acc), ASSET_ID, spender_acc, 1000);
+        let change_result = SecondErc20::approve(Origin::signed(owner_acc), ASSET_ID, spender_acc, 500);
+
+        assert_ok!(first_result, ().into());
+        assert_noop!(change_result, Error::<Test, Instance1>::UnsafeAllowanceChange);
+
+        let reset_result = SecondErc20::approve(Origin::signed(owner_acc), ASSET_ID, spender_acc, 0);
+        let change_result = SecondErc20::approve(Origin::signed(owner_acc), ASSET_ID, spender_acc, 500);
+        let checked_result = SecondErc20::approve_checked(Origin::signed(owner_acc), ASSET_ID, spender_acc, 500, 700);
+
+        assert_ok!(reset_result, ().into());
+        assert_ok!(change_result, ().into());
+        assert_ok!(checked_result, ().into());
+        assert_eq!(700, SecondErc20::get_allowance((ASSET_ID, owner_acc), spender_acc));
+    });
+}
+
+#[test]
+fn it_fails_transfer_and_mint_to_sentinel_account() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = BALANCES[0].0;
+        let sentinel_acc = 0;
+
+        let transfer_result = PalletErc20::transfer(Origin::signed(sender_acc), ASSET_ID, sentinel_acc, 100);
+        let mint_result = PalletErc20::mint(Origin::root(), ASSET_ID, sentinel_acc, 100);
+        let transfer_from_sentinel_result = PalletErc20::transfer(Origin::signed(sentinel_acc), ASSET_ID, sender_acc, 0);
+
+        assert_noop!(transfer_result, RuntimeError::SentinelAccount);
+        assert_noop!(mint_result, RuntimeError::SentinelAccount);
+        assert_noop!(transfer_from_sentinel_result, RuntimeError::SentinelAccount);
+        assert_eq!(0, PalletErc20::get_balance(ASSET_ID, sentinel_acc));
+    });
+}
+
+#[test]
+fn it_works_self_transfer_reports_unchanged_balance() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = BALANCES[0].0;
+
+        let transfer_result = PalletErc20::transfer(Origin::signed(sender_acc), ASSET_ID, sender_acc, 500);
+        let check_event = Event::pallet_erc20(crate::Event::Transfer {
+            asset_id: ASSET_ID,
+            from: sender_acc,
+            to: sender_acc,
+            amount: 500,
+            from_balance: BALANCES[0].1,
+            to_balance: BALANCES[0].1,
+        });
+
+        assert_ok!(transfer_result, ().into());
+        assert_eq!(BALANCES[0].1, PalletErc20::get_balance(ASSET_ID, sender_acc));
+        assert_eq!(Ok(check_event), last_event());
+    });
+}
+
+#[test]
+fn it_fails_self_and_zero_transfer_when_forbidden() {
+    new_test_ext().execute_with(|| {
+        let sender_acc = SECOND_BALANCES[0].0;
+        let reciever_acc = SECOND_BALANCES[1].0;
+
+        let self_result = SecondErc20::transfer(Origin::signed(sender_acc), ASSET_ID, sender_acc, 100);
+        let zero_result = SecondErc20::transfer(Origin::signed(sender_acc), ASSET_ID, reciever_acc, 0);
+        let default_zero_result = PalletErc20::transfer(Origin::signed(BALANCES[0].0), ASSET_ID, BALANCES[1].0, 0);
+
+        assert_noop!(self_result, Error::<Test, Instance1>::SelfTransfer);
+        assert_noop!(zero_result, Error::<Test, Instance1>::ZeroAmount);
+        assert_ok!(default_zero_result, ().into());
+        assert_eq!(SECOND_BALANCES[0].1, SecondErc20::get_balance(ASSET_ID, sender_acc));
+    });
+}
+
+#[test]
+fn it_works_migration_v2_writes_genesis_decimals() {
+    new_test_ext().execute_with(|| {
+        // chain built before genesis decimals were stored
+        Decimals::<Test>::remove(ASSET_ID);
+        Decimals::<Test, Instance1>::remove(ASSET_ID);
+        PalletVersion::new(0, 1, 0).put_into_storage::<PalletInfo, PalletErc20>();
+
+        MigrateToV2::<Test, (), MigratedDecimals>::on_runtime_upgrade();
+        MigrateToV2::<Test, Instance1, MigratedDecimals>::on_runtime_upgrade();
+
+        assert_eq!(9, PalletErc20::get_decimals(ASSET_ID));
+        assert_eq!(9, SecondErc20::get_decimals(ASSET_ID));
+        assert_eq!(get_test_total_supply(), PalletErc20::get_total_supply(ASSET_ID));
+        assert_eq!(BALANCES[0].1, PalletErc20::get_balance(ASSET_ID, BALANCES[0].0));
+    });
+}
+
+#[test]
+fn it_works_migration_v2_keeps_stored_decimals() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(None, PalletErc20::storage_version());
+
+        MigrateToV2::<Test, (), MigratedDecimals>::on_runtime_upgrade();
+        MigrateToV2::<Test, Instance1, MigratedDecimals>::on_runtime_upgrade();
+
+        assert_eq!(DEFAULT_DECIMALS, PalletErc20::get_decimals(ASSET_ID));
+        assert_eq!(SECOND_DECIMALS, SecondErc20::get_decimals(ASSET_ID));
+    });
+}
+
+#[test]
+fn it_works_migration_v2_skips_current_version() {
+    new_test_ext().execute_with(|| {
+        Decimals::<Test>::remove(ASSET_ID);
+        PalletErc20::current_version().put_into_storage::<PalletInfo, PalletErc20>();
+
+        MigrateToV2::<Test, (), MigratedDecimals>::on_runtime_upgrade();
+
+        assert!(!Decimals::<Test>::contains_key(ASSET_ID));
+        assert_eq!(DEFAULT_DECIMALS, PalletErc20::get_decimals(ASSET_ID));
+    });
+}
+
+#[test]
+fn it_works_migration_v2_from_single_token_layout() {
+    new_single_token_ext().execute_with(|| {
+        let (owner_acc, spender_acc) = (BALANCES[0].0, BALANCES[1].0);
+
+        assert_eq!(Ok(()), MigrateToV2::<Test, (), MigratedDecimals>::pre_upgrade());
+        assert_eq!(Ok(()), MigrateToV2::<Test, Instance1, MigratedDecimals>::pre_upgrade());
+
+        MigrateToV2::<Test, (), MigratedDecimals>::on_runtime_upgrade();
+        MigrateToV2::<Test, Instance1, MigratedDecimals>::on_runtime_upgrade();
+
+        assert_eq!(Ok(()), MigrateToV2::<Test, (), MigratedDecimals>::post_upgrade());
+        assert_eq!(Ok(()), MigrateToV2::<Test, Instance1, MigratedDecimals>::post_upgrade());
+        assert_eq!(1, PalletErc20::get_next_asset_id());
+        assert_eq!(get_test_total_supply(), PalletErc20::get_total_supply(ASSET_ID));
+        assert_eq!(get_test_token_name(), PalletErc20::get_name(ASSET_ID));
+        assert_eq!(get_test_token_sym(), PalletErc20::get_symbol(ASSET_ID));
+        assert_eq!(9, PalletErc20::get_decimals(ASSET_ID));
+        BALANCES.iter().for_each(|(acc, bal)| {
+            assert_eq!(*bal, PalletErc20::get_balance(ASSET_ID, acc));
+        });
+        assert!(!crate::BalanceOf::<Test>::contains_key(ASSET_ID, BALANCES[3].0));
+        assert_eq!(SINGLE_TOKEN_ALLOWANCE, PalletErc20::get_allowance((ASSET_ID, owner_acc), spender_acc));
+        assert_eq!(1, crate::AllowanceOf::<Test>::iter().count());
+        assert_eq!(BALANCES.len() - 1, crate::BalanceOf::<Test>::iter().count());
+
+        // the instance added by the upgrade has no token to migrate
+        assert_eq!(0, SecondErc20::get_next_asset_id());
+        assert!(!Decimals::<Test, Instance1>::contains_key(ASSET_ID));
+
+        let transfer_result = PalletErc20::transfer(Origin::signed(owner_acc), ASSET_ID, BALANCES[3].0, 1000);
+
+        assert_ok!(transfer_result, ().into());
+        assert_eq!(BALANCES[0].1 - 1000, PalletErc20::get_balance(ASSET_ID, owner_acc));
+    });
+}
+
+#[test]
+fn it_fails_migration_v2_checks_on_mixed_layout() {
+    new_single_token_ext().execute_with(|| {
+        NextAssetId::<Test>::put(1);
+
+        assert!(MigrateToV2::<Test, (), MigratedDecimals>::pre_upgrade().is_err());
+        assert!(MigrateToV2::<Test, (), MigratedDecimals>::post_upgrade().is_err());
+    });
+}
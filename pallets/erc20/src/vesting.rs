@@ -0,0 +1,39 @@
+//! Vesting schedules locking part of a token balance until it is released block by block
+
+use frame_support::{
+	codec::{Encode, Decode},
+	sp_runtime::traits::{AtLeast32BitUnsigned, Convert},
+	RuntimeDebug,
+};
+
+/// Vesting schedule of an account: `locked` tokens are released by `per_block`
+/// every block since `starting_block`, nothing is released before the cliff ends
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct VestingInfo<Balance, BlockNumber> {
+	/// Total amount locked at the start of the schedule
+	pub locked: Balance,
+	/// Amount released every block after the start
+	pub per_block: Balance,
+	/// Block the release starts counting from
+	pub starting_block: BlockNumber,
+	/// Number of blocks after the start during which nothing is released
+	pub cliff: BlockNumber,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy, BlockNumber: AtLeast32BitUnsigned + Copy>
+	VestingInfo<Balance, BlockNumber>
+{
+	/// Amount still locked at block `n`
+	pub fn locked_at<BlockNumberToBalance: Convert<BlockNumber, Balance>>(&self, n: BlockNumber) -> Balance {
+		if n < self.starting_block.saturating_add(self.cliff) {
+			return self.locked;
+		}
+		let vested_blocks = BlockNumberToBalance::convert(n.saturating_sub(self.starting_block));
+		self.locked.saturating_sub(vested_blocks.saturating_mul(self.per_block))
+	}
+
+	/// Whether the schedule releases anything at all
+	pub fn is_valid(&self) -> bool {
+		!self.locked.is_zero() && !self.per_block.is_zero()
+	}
+}
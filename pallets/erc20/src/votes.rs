@@ -0,0 +1,49 @@
+//! Voting power of token holders derived from delegation checkpoints
+
+use super::*;
+use frame_support::sp_runtime::traits::Zero;
+
+/// Read access to delegated voting power, implemented by the pallet so that
+/// governance pallets can weight votes by token holdings
+pub trait Votes<AccountId, BlockNumber> {
+	/// Token identifier
+	type AssetId;
+	/// Voting power unit
+	type Balance;
+
+	/// Current voting power delegated to `who`
+	fn get_votes(asset: Self::AssetId, who: &AccountId) -> Self::Balance;
+
+	/// Voting power delegated to `who` at the end of `block`,
+	/// `None` if `block` is not finished yet
+	fn get_past_votes(asset: Self::AssetId, who: &AccountId, block: BlockNumber) -> Option<Self::Balance>;
+}
+
+impl<T: Config<I>, I: 'static> Votes<T::AccountId, T::BlockNumber> for Pallet<T, I> {
+	type AssetId = T::AssetId;
+	type Balance = T::Balance;
+
+	fn get_votes(asset: Self::AssetId, who: &T::AccountId) -> Self::Balance {
+		NumCheckpoints::<T, I>::get(asset, who)
+			.checked_sub(1)
+			.and_then(|index| Checkpoints::<T, I>::get(asset, (who.clone(), index)))
+			.map(|(_, votes)| votes)
+			.unwrap_or_else(Zero::zero)
+	}
+
+	fn get_past_votes(asset: Self::AssetId, who: &T::AccountId, block: T::BlockNumber) -> Option<Self::Balance> {
+		if block >= frame_system::Module::<T>::block_number() {
+			return None;
+		}
+		let checkpoint = |index| Checkpoints::<T, I>::get(asset, (who.clone(), index));
+		let passed = partition_point(
+			NumCheckpoints::<T, I>::get(asset, who),
+			|index| matches!(checkpoint(index), Some((checkpoint_block, _)) if checkpoint_block <= block)
+		);
+		let votes = passed.checked_sub(1)
+			.and_then(checkpoint)
+			.map(|(_, votes)| votes)
+			.unwrap_or_else(Zero::zero);
+		Some(votes)
+	}
+}
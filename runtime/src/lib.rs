@@ -13,7 +13,7 @@ use sp_runtime::{
 	transaction_validity::{TransactionValidity, TransactionSource},
 };
 use sp_runtime::traits::{
-	AccountIdLookup, BlakeTwo256, Block as BlockT, Verify, IdentifyAccount, NumberFor,
+	AccountIdLookup, BlakeTwo256, Block as BlockT, ConvertInto, Verify, IdentifyAccount, NumberFor,
 };
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -28,7 +28,7 @@ use sp_version::NativeVersion;
 pub use sp_runtime::BuildStorage;
 pub use pallet_timestamp::Call as TimestampCall;
 pub use pallet_balances::Call as BalancesCall;
-pub use sp_runtime::{Permill, Perbill};
+pub use sp_runtime::{Permill, Perbill, FixedPointNumber, FixedU128};
 pub use frame_support::{
 	construct_runtime, parameter_types, StorageValue,
 	traits::{KeyOwnerProofSystem, Randomness},
@@ -37,7 +37,6 @@ pub use frame_support::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 	},
 };
-use pallet_transaction_payment::CurrencyAdapter;
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -56,6 +55,9 @@ pub type AccountIndex = u32;
 /// Balance of an account.
 pub type Balance = u128;
 
+/// Identifier of an ERC20 token.
+pub type AssetId = u32;
+
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
@@ -242,10 +244,16 @@ impl pallet_balances::Config for Runtime {
 
 parameter_types! {
 	pub const TransactionByteFee: Balance = 1;
+	/// Token of `PalletERC20` used to pay transaction fees
+	pub const FeeAssetId: AssetId = 0;
+	/// Amount of fee tokens charged for a unit of native fee
+	pub FeeConversionRate: FixedU128 = FixedU128::saturating_from_integer(1);
 }
 
 impl pallet_transaction_payment::Config for Runtime {
-	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
+	/// Fees are paid in the ERC20 token, use `pallet_transaction_payment::CurrencyAdapter<Balances, ()>`
+	/// to charge them in the native balance instead.
+	type OnChargeTransaction = pallet_erc20::Erc20FeeAdapter<Runtime, (), FeeAssetId, FeeConversionRate>;
 	type TransactionByteFee = TransactionByteFee;
 	type WeightToFee = IdentityFee<Balance>;
 	type FeeMultiplierUpdate = ();
@@ -258,9 +266,136 @@ impl pallet_sudo::Config for Runtime {
 
 pub use pallet_erc20;
 
+parameter_types! {
+	pub const StringLimit: u32 = 50;
+	pub const TokenMinimumBalance: Balance = 1_000_000_000;
+	pub const StableTokenMinimumBalance: Balance = 1;
+	pub const GovTokenMinimumBalance: Balance = 1_000;
+	/// One whole token of each instance
+	pub const TokenMinVestedTransfer: Balance = 1_000_000_000_000_000_000;
+	pub const StableTokenMinVestedTransfer: Balance = 1_000_000;
+	pub const GovTokenMinVestedTransfer: Balance = 1_000_000_000_000;
+	pub const MaxBatchSize: u32 = 256;
+	pub const MaxSpenders: u32 = 128;
+	pub const StrictApprove: bool = false;
+	pub const ForbidSelfTransfer: bool = false;
+	pub const ForbidZeroAmount: bool = false;
+	/// Genesis decimals of each token in the chain spec, written by the v2 migration
+	pub const TokenGenesisDecimals: u8 = 18;
+	pub const StableTokenGenesisDecimals: u8 = 6;
+	pub const GovTokenGenesisDecimals: u8 = 12;
+}
+
+/// Signs the payloads of the ERC20 signed call benchmarks with a new sr25519 key of the keystore
+#[cfg(feature = "runtime-benchmarks")]
+pub struct Erc20BenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_erc20::BenchmarkHelper<AccountId, Signature> for Erc20BenchmarkHelper {
+	fn signer() -> AccountId {
+		use sp_runtime::{app_crypto::RuntimePublic, MultiSigner};
+		let public = sp_core::sr25519::Public::generate_pair(ERC20_BENCHMARK_KEY, None);
+		MultiSigner::from(public).into_account()
+	}
+
+	fn sign(signer: &AccountId, payload: &[u8]) -> Signature {
+		use sp_runtime::app_crypto::RuntimePublic;
+		let public = sp_core::sr25519::Public::from_raw(signer.clone().into());
+		public.sign(ERC20_BENCHMARK_KEY, &payload)
+			.expect("key of the signer is in the keystore")
+			.into()
+	}
+}
+
+/// Key type of the keys generated by Erc20BenchmarkHelper
+#[cfg(feature = "runtime-benchmarks")]
+const ERC20_BENCHMARK_KEY: KeyTypeId = KeyTypeId(*b"erc2");
+
 impl pallet_erc20::Config for Runtime {
 	type Event = Event;
 	type Balance = Balance;
+	type AssetId = AssetId;
+	type CreateOrigin = frame_system::EnsureRoot<AccountId>;
+	type MintOrigin = frame_system::EnsureRoot<AccountId>;
+	type BurnOrigin = frame_system::EnsureRoot<AccountId>;
+	type MetadataOrigin = frame_system::EnsureRoot<AccountId>;
+	type PauseOrigin = frame_system::EnsureRoot<AccountId>;
+	type CapOrigin = frame_system::EnsureRoot<AccountId>;
+	type SnapshotOrigin = frame_system::EnsureRoot<AccountId>;
+	type StringLimit = StringLimit;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = TokenMinVestedTransfer;
+	type MinimumBalance = TokenMinimumBalance;
+	type DustRemoval = ();
+	type MaxLocks = MaxLocks;
+	type MaxBatchSize = MaxBatchSize;
+	type MaxSpenders = MaxSpenders;
+	type StrictApprove = StrictApprove;
+	type ForbidSelfTransfer = ForbidSelfTransfer;
+	type ForbidZeroAmount = ForbidZeroAmount;
+	type Signature = Signature;
+	type Signer = <Signature as Verify>::Signer;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = Erc20BenchmarkHelper;
+	type WeightInfo = pallet_erc20::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_erc20::Config<pallet_erc20::Instance1> for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type CreateOrigin = frame_system::EnsureRoot<AccountId>;
+	type MintOrigin = frame_system::EnsureRoot<AccountId>;
+	type BurnOrigin = frame_system::EnsureRoot<AccountId>;
+	type MetadataOrigin = frame_system::EnsureRoot<AccountId>;
+	type PauseOrigin = frame_system::EnsureRoot<AccountId>;
+	type CapOrigin = frame_system::EnsureRoot<AccountId>;
+	type SnapshotOrigin = frame_system::EnsureRoot<AccountId>;
+	type StringLimit = StringLimit;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = StableTokenMinVestedTransfer;
+	type MinimumBalance = StableTokenMinimumBalance;
+	type DustRemoval = ();
+	type MaxLocks = MaxLocks;
+	type MaxBatchSize = MaxBatchSize;
+	type MaxSpenders = MaxSpenders;
+	type StrictApprove = StrictApprove;
+	type ForbidSelfTransfer = ForbidSelfTransfer;
+	type ForbidZeroAmount = ForbidZeroAmount;
+	type Signature = Signature;
+	type Signer = <Signature as Verify>::Signer;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = Erc20BenchmarkHelper;
+	type WeightInfo = pallet_erc20::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_erc20::Config<pallet_erc20::Instance2> for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type CreateOrigin = frame_system::EnsureRoot<AccountId>;
+	type MintOrigin = frame_system::EnsureRoot<AccountId>;
+	type BurnOrigin = frame_system::EnsureRoot<AccountId>;
+	type MetadataOrigin = frame_system::EnsureRoot<AccountId>;
+	type PauseOrigin = frame_system::EnsureRoot<AccountId>;
+	type CapOrigin = frame_system::EnsureRoot<AccountId>;
+	type SnapshotOrigin = frame_system::EnsureRoot<AccountId>;
+	type StringLimit = StringLimit;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = GovTokenMinVestedTransfer;
+	type MinimumBalance = GovTokenMinimumBalance;
+	type DustRemoval = ();
+	type MaxLocks = MaxLocks;
+	type MaxBatchSize = MaxBatchSize;
+	type MaxSpenders = MaxSpenders;
+	type StrictApprove = StrictApprove;
+	type ForbidSelfTransfer = ForbidSelfTransfer;
+	type ForbidZeroAmount = ForbidZeroAmount;
+	type Signature = Signature;
+	type Signer = <Signature as Verify>::Signer;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = Erc20BenchmarkHelper;
+	type WeightInfo = pallet_erc20::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -279,6 +414,8 @@ construct_runtime!(
 		TransactionPayment: pallet_transaction_payment::{Module, Storage},
 		Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
 		PalletERC20: pallet_erc20::{Module, Call, Event<T>, Storage, Config<T>},
+		StableToken: pallet_erc20::<Instance1>::{Module, Call, Event<T>, Storage, Config<T>},
+		GovToken: pallet_erc20::<Instance2>::{Module, Call, Event<T>, Storage, Config<T>},
 	}
 );
 
@@ -313,7 +450,27 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllModules,
+	Erc20Migrations,
 >;
+/// Storage migrations of the erc20 instances, run before the pallets store their new version.
+pub type Erc20Migrations = (
+	pallet_erc20::migrations::v2::MigrateToV2<Runtime, (), TokenGenesisDecimals>,
+	pallet_erc20::migrations::v2::MigrateToV2<Runtime, pallet_erc20::Instance1, StableTokenGenesisDecimals>,
+	pallet_erc20::migrations::v2::MigrateToV2<Runtime, pallet_erc20::Instance2, GovTokenGenesisDecimals>,
+);
+
+/// Runs `$query` against the erc20 instance selected by the `Erc20Api` `instance` index,
+/// naming its pallet `$token`; `None` for an unknown instance.
+macro_rules! with_erc20_instance {
+	($instance:expr, $token:ident => $query:expr) => {
+		match $instance {
+			0 => { type $token = PalletERC20; Some($query) }
+			1 => { type $token = StableToken; Some($query) }
+			2 => { type $token = GovToken; Some($query) }
+			_ => None,
+		}
+	};
+}
 
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
@@ -447,6 +604,53 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_erc20_rpc_runtime_api::Erc20Api<Block, AccountId, AssetId, Balance> for Runtime {
+		fn balance_of(instance: u8, asset_id: AssetId, who: AccountId) -> Option<Balance> {
+			with_erc20_instance!(instance, Token => Token::get_balance(asset_id, who))
+		}
+
+		fn allowance(instance: u8, asset_id: AssetId, owner: AccountId, spender: AccountId) -> Option<Balance> {
+			with_erc20_instance!(instance, Token => Token::get_allowance((asset_id, owner), spender))
+		}
+
+		fn total_supply(instance: u8, asset_id: AssetId) -> Option<Balance> {
+			with_erc20_instance!(instance, Token => Token::get_total_supply(asset_id))
+		}
+
+		fn name(instance: u8, asset_id: AssetId) -> Option<Vec<u8>> {
+			with_erc20_instance!(instance, Token => Token::get_name(asset_id))
+		}
+
+		fn symbol(instance: u8, asset_id: AssetId) -> Option<Vec<u8>> {
+			with_erc20_instance!(instance, Token => Token::get_symbol(asset_id))
+		}
+
+		fn decimals(instance: u8, asset_id: AssetId) -> Option<u8> {
+			with_erc20_instance!(instance, Token => Token::get_decimals(asset_id))
+		}
+
+		fn balance_of_at(
+			instance: u8,
+			asset_id: AssetId,
+			who: AccountId,
+			snapshot_id: pallet_erc20::SnapshotId,
+		) -> Option<Option<Balance>> {
+			with_erc20_instance!(instance, Token => Token::balance_of_at(asset_id, &who, snapshot_id))
+		}
+
+		fn total_supply_at(
+			instance: u8,
+			asset_id: AssetId,
+			snapshot_id: pallet_erc20::SnapshotId,
+		) -> Option<Option<Balance>> {
+			with_erc20_instance!(instance, Token => Token::total_supply_at(asset_id, snapshot_id))
+		}
+
+		fn allowances(instance: u8, asset_id: AssetId, owner: AccountId) -> Option<Vec<(AccountId, Balance)>> {
+			with_erc20_instance!(instance, Token => Token::allowances_of(asset_id, &owner))
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(
@@ -481,6 +685,7 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+			add_benchmark!(params, batches, pallet_erc20, PalletERC20);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)